@@ -0,0 +1,322 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use sqlx::{
+    Connection, SqlitePool,
+    sqlite::{SqliteConnectOptions, SqliteConnection},
+};
+use tauri::{AppHandle, State};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    DatabaseManager,
+    constants::{BACKUP_INTERVAL_HOURS_KEY, BACKUP_KEEP_COUNT_KEY},
+    error::OneClickLaunchError,
+    setting_registry,
+};
+
+/// 备份文件扩展名
+const BACKUP_EXTENSION: &str = "db";
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct BackupVo {
+    pub file_name: String,
+    pub path: String,
+    /// 文件大小(字节)
+    pub size: u64,
+    /// 备份时间(Unix秒)
+    pub created_at: i64,
+}
+
+/// 立即备份数据库,返回备份文件名
+#[tauri::command]
+pub async fn create_backup(db: State<'_, DatabaseManager>) -> Result<String, OneClickLaunchError> {
+    let (pool, db_path) = db.pool_and_path();
+    let path = backup_database(&pool, &db_path, "manual").await?;
+    rotate_backups(&pool, &db_path).await?;
+    Ok(file_name_of(&path))
+}
+
+/// 查询全部备份,按备份时间倒序排列
+#[tauri::command]
+pub async fn list_backups(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<BackupVo>, OneClickLaunchError> {
+    let backups = find_backups(&db.db_path())?
+        .into_iter()
+        .map(|path| {
+            let metadata = fs::metadata(&path).ok();
+            BackupVo {
+                file_name: file_name_of(&path),
+                path: path.to_string_lossy().to_string(),
+                size: metadata.as_ref().map(|m| m.len()).unwrap_or_default(),
+                created_at: metadata
+                    .and_then(|m| m.modified().ok())
+                    .map(unix_secs)
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+    Ok(backups)
+}
+
+/// 从备份恢复数据库,恢复前会先备份当前数据库,恢复完成后重启应用程序
+#[tauri::command]
+pub async fn restore_backup(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    file_name: String,
+) -> Result<(), OneClickLaunchError> {
+    let backup_path = find_backups(&db.db_path())?
+        .into_iter()
+        .find(|path| file_name_of(path) == file_name)
+        .ok_or_else(|| OneClickLaunchError::ExecutionError(format!("备份不存在: {}", file_name)))?;
+
+    if !check_integrity(&backup_path).await {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "备份文件已损坏: {}",
+            file_name
+        )));
+    }
+
+    backup_database(&db.pool(), &db.db_path(), "pre-restore").await?;
+
+    // 关闭连接池后才能替换数据库文件
+    db.pool().close().await;
+
+    replace_database(&db.db_path(), &backup_path)?;
+
+    info!("已从备份恢复数据库: {:?}", backup_path);
+
+    app.restart();
+}
+
+/// 使用`VACUUM INTO`在线备份数据库,返回备份文件路径
+///
+/// `reason`会作为备份文件名的一部分,用于区分定时备份、迁移前备份等
+pub async fn backup_database(
+    pool: &SqlitePool,
+    db_path: &Path,
+    reason: &str,
+) -> Result<PathBuf, OneClickLaunchError> {
+    let dir = backup_dir(db_path);
+    fs::create_dir_all(&dir)?;
+
+    let (timestamp,): (String,) =
+        sqlx::query_as("SELECT strftime('%Y%m%d-%H%M%S','now','localtime')")
+            .fetch_one(pool)
+            .await?;
+
+    let path = dir.join(format!(
+        "{}-{}-{}.{}",
+        backup_prefix(db_path),
+        timestamp,
+        reason,
+        BACKUP_EXTENSION
+    ));
+
+    // 同一秒内重复备份时覆盖之前的文件, VACUUM INTO要求目标文件不存在
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(path.to_string_lossy().to_string())
+        .execute(pool)
+        .await?;
+
+    info!("数据库已备份到: {:?}", path);
+
+    Ok(path)
+}
+
+/// 按设置的保留数量删除最旧的备份
+pub async fn rotate_backups(pool: &SqlitePool, db_path: &Path) -> Result<(), OneClickLaunchError> {
+    let keep_count = setting_registry::read_integer(pool, BACKUP_KEEP_COUNT_KEY).await? as usize;
+
+    for path in find_backups(db_path)?.into_iter().skip(keep_count) {
+        debug!("删除过期备份: {:?}", path);
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// 定时备份: 距离最近一次备份超过设置的间隔时备份数据库,间隔为0时不自动备份
+pub async fn run_scheduled_backup(
+    pool: &SqlitePool,
+    db_path: &Path,
+) -> Result<(), OneClickLaunchError> {
+    let interval_hours =
+        setting_registry::read_integer(pool, BACKUP_INTERVAL_HOURS_KEY).await? as u64;
+
+    if interval_hours == 0 {
+        debug!("run_scheduled_backup 未开启定时备份");
+        return Ok(());
+    }
+
+    let newest = find_backups(db_path)?
+        .first()
+        .and_then(|path| fs::metadata(path).ok())
+        .and_then(|m| m.modified().ok());
+
+    let due = newest
+        .and_then(|t| t.elapsed().ok())
+        .is_none_or(|elapsed| elapsed >= Duration::from_secs(interval_hours * 60 * 60));
+
+    if due {
+        backup_database(pool, db_path, "scheduled").await?;
+        rotate_backups(pool, db_path).await?;
+    }
+
+    Ok(())
+}
+
+/// 启动时检查数据库文件是否损坏,损坏时询问用户是否从最近的有效备份恢复
+///
+/// 损坏的数据库文件不会被删除,而是重命名后保留在原目录中;
+/// `interactive`为false时(如命令行命令)不弹窗,直接返回错误
+pub async fn ensure_database_integrity(
+    db_path: &Path,
+    interactive: bool,
+) -> Result<(), OneClickLaunchError> {
+    let is_empty = fs::metadata(db_path).map(|m| m.len() == 0).unwrap_or(true);
+    if is_empty || check_integrity(db_path).await {
+        return Ok(());
+    }
+
+    error!("数据库文件已损坏: {:?}", db_path);
+
+    if !interactive {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "数据库文件已损坏,请启动应用程序从备份恢复: {}",
+            db_path.to_string_lossy()
+        )));
+    }
+
+    let mut newest_valid = None;
+    for path in find_backups(db_path)? {
+        if check_integrity(&path).await {
+            newest_valid = Some(path);
+            break;
+        }
+    }
+
+    let Some(backup_path) = newest_valid else {
+        warn!("没有找到可用于恢复的有效备份");
+        return Ok(());
+    };
+
+    let confirmed = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title(crate::constants::APPLICATION_NAME)
+        .set_description(format!(
+            "数据库文件已损坏，是否从最近的备份恢复？\n\n备份: {}\n\n损坏的文件会被重命名保留。",
+            file_name_of(&backup_path)
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+
+    if confirmed == rfd::MessageDialogResult::Yes {
+        let corrupted_path =
+            db_path.with_extension(format!("corrupted-{}", unix_secs(SystemTime::now())));
+        fs::rename(db_path, &corrupted_path)?;
+        replace_database(db_path, &backup_path)?;
+        info!(
+            "已从备份恢复数据库: {:?}, 损坏的文件已保存为: {:?}",
+            backup_path, corrupted_path
+        );
+    }
+
+    Ok(())
+}
+
+/// 检查数据库文件的完整性
+pub async fn check_integrity(path: &Path) -> bool {
+    let Ok(options) = SqliteConnectOptions::from_str(&path.to_string_lossy()) else {
+        return false;
+    };
+
+    let Ok(mut conn) = SqliteConnection::connect_with(&options.read_only(true)).await else {
+        return false;
+    };
+
+    let result: Result<(String,), sqlx::Error> = sqlx::query_as("PRAGMA quick_check")
+        .fetch_one(&mut conn)
+        .await;
+
+    let _ = conn.close().await;
+
+    matches!(result, Ok((ref status,)) if status == "ok")
+}
+
+/// 用备份文件替换数据库文件,并清理旧的WAL日志文件
+fn replace_database(db_path: &Path, backup_path: &Path) -> Result<(), OneClickLaunchError> {
+    for suffix in ["-wal", "-shm"] {
+        let mut journal = db_path.as_os_str().to_owned();
+        journal.push(suffix);
+        let journal = PathBuf::from(journal);
+        if journal.exists() {
+            fs::remove_file(journal)?;
+        }
+    }
+    fs::copy(backup_path, db_path)?;
+    Ok(())
+}
+
+/// 备份目录: 数据库文件所在目录下的backups目录
+fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .map(|dir| dir.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"))
+}
+
+/// 备份文件名前缀,使用数据库文件名区分不同数据库的备份
+fn backup_prefix(db_path: &Path) -> String {
+    db_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 查找数据库的全部备份,按备份时间倒序排列
+fn find_backups(db_path: &Path) -> Result<Vec<PathBuf>, OneClickLaunchError> {
+    let dir = backup_dir(db_path);
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let prefix = format!("{}-", backup_prefix(db_path));
+
+    let mut backups = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            let file_name = file_name_of(path);
+            file_name.starts_with(&prefix)
+                && path.extension().is_some_and(|ext| ext == BACKUP_EXTENSION)
+        })
+        .collect::<Vec<PathBuf>>();
+
+    // 文件名中包含备份时间,按文件名倒序即按时间倒序
+    backups.sort_by_key(|path| std::cmp::Reverse(file_name_of(path)));
+
+    Ok(backups)
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn unix_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
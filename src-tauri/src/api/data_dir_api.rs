@@ -0,0 +1,156 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tauri::{AppHandle, State};
+use tracing::info;
+
+use crate::{
+    DatabaseManager,
+    error::OneClickLaunchError,
+    paths::{self, PORTABLE_MARKER_FILE},
+};
+
+/// 数据存放位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataLocation {
+    /// 可执行文件所在目录
+    Portable,
+    /// 系统的用户数据目录
+    Installed,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct DataDirVo {
+    /// 当前使用的数据目录
+    pub data_dir: String,
+    /// 未指定数据目录时使用的位置
+    pub location: DataLocation,
+    /// 是否通过`--data-dir`或环境变量指定了数据目录
+    pub overridden: bool,
+}
+
+/// 查询当前使用的数据目录
+#[tauri::command]
+pub async fn query_data_dir(
+    db: State<'_, DatabaseManager>,
+) -> Result<DataDirVo, OneClickLaunchError> {
+    Ok(DataDirVo {
+        data_dir: db.data_dir.to_string_lossy().to_string(),
+        location: current_location(),
+        overridden: db.data_dir != paths::default_data_dir()?,
+    })
+}
+
+/// 把数据迁移到便携版或安装版的位置,迁移完成后重启应用程序
+///
+/// 迁移到便携版时在可执行文件所在目录下创建标记文件;
+/// 迁移到安装版时删除标记文件,原来的`data`目录重命名后保留,以免下次启动仍被识别为便携版
+#[tauri::command]
+pub async fn migrate_data_dir(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    target: DataLocation,
+) -> Result<(), OneClickLaunchError> {
+    if db.data_dir != paths::default_data_dir()? {
+        return Err(OneClickLaunchError::ExecutionError(
+            "已通过--data-dir或环境变量指定数据目录,无法迁移".to_string(),
+        ));
+    }
+    if target == current_location() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "数据已在目标位置".to_string(),
+        ));
+    }
+
+    let target_dir = match target {
+        DataLocation::Portable => paths::portable_data_dir()?,
+        DataLocation::Installed => paths::installed_data_dir()?,
+    };
+    if target_dir.is_dir() && fs::read_dir(&target_dir)?.next().is_some() {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "目标位置已存在数据: {}",
+            target_dir.to_string_lossy()
+        )));
+    }
+
+    // 当前配置的数据库正在使用,使用VACUUM INTO复制,其余文件直接复制
+    let db_path = db.db_path();
+    copy_dir(&db.data_dir, &target_dir, &db_path)?;
+
+    let target_db_path = target_dir.join(db_path.strip_prefix(&db.data_dir).map_err(|_| {
+        OneClickLaunchError::ExecutionError(format!(
+            "数据库不在数据目录中: {}",
+            db_path.to_string_lossy()
+        ))
+    })?);
+    if let Some(parent) = target_db_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    sqlx::query("VACUUM INTO ?")
+        .bind(target_db_path.to_string_lossy().to_string())
+        .execute(&db.pool())
+        .await?;
+
+    let marker = paths::exe_dir()?.join(PORTABLE_MARKER_FILE);
+    match target {
+        DataLocation::Portable => fs::write(&marker, "")?,
+        DataLocation::Installed => {
+            if marker.exists() {
+                fs::remove_file(&marker)?;
+            }
+
+            // 关闭连接池后才能重命名数据库所在目录
+            db.pool().close().await;
+
+            let secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            let mut migrated = db.data_dir.as_os_str().to_owned();
+            migrated.push(format!(".migrated-{}", secs));
+            fs::rename(&db.data_dir, PathBuf::from(migrated))?;
+        }
+    }
+
+    info!("数据已迁移到: {:?}", target_dir);
+
+    app.restart();
+}
+
+fn current_location() -> DataLocation {
+    if paths::is_portable() {
+        DataLocation::Portable
+    } else {
+        DataLocation::Installed
+    }
+}
+
+/// 递归复制目录,跳过正在使用的数据库文件及其WAL日志文件
+fn copy_dir(from: &Path, to: &Path, skip_db: &Path) -> Result<(), OneClickLaunchError> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+
+        if path.is_dir() {
+            copy_dir(&path, &target, skip_db)?;
+        } else if !is_db_file(&path, skip_db) {
+            fs::copy(&path, &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_db_file(path: &Path, db_path: &Path) -> bool {
+    ["", "-wal", "-shm"].iter().any(|suffix| {
+        let mut file = db_path.as_os_str().to_owned();
+        file.push(suffix);
+        path == Path::new(&file)
+    })
+}
@@ -0,0 +1,279 @@
+use std::{
+    process::{Output, Stdio},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::{io::AsyncWriteExt, process::Command, time::timeout};
+use tracing::{info, warn};
+
+use crate::{
+    DatabaseManager,
+    db::hook::{self, Hook},
+    error::OneClickLaunchError,
+    events::{
+        Event,
+        types::{ApplicationStartupComplete, LauncherLaunched, SettingUpdated},
+    },
+};
+
+/// 钩子命令的默认超时时间(秒)
+const DEFAULT_TIMEOUT_SECS: i64 = 30;
+
+/// 钩子命令的最长超时时间(秒)
+const MAX_TIMEOUT_SECS: i64 = 3600;
+
+/// 传给钩子命令的环境变量前缀
+const HOOK_ENV_PREFIX: &str = "ONE_CLICK_LAUNCH_";
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct HookVo {
+    pub id: i64,
+    pub event: String,
+    pub command: String,
+    pub timeout_secs: i64,
+    pub enabled: bool,
+}
+
+impl From<Hook> for HookVo {
+    fn from(hook: Hook) -> Self {
+        Self {
+            id: hook.id,
+            event: hook.event,
+            command: hook.command,
+            timeout_secs: hook.timeout_secs,
+            enabled: hook.enabled,
+        }
+    }
+}
+
+/// 可以设置钩子的事件
+pub fn hook_events() -> [&'static str; 3] {
+    [
+        LauncherLaunched::name(),
+        ApplicationStartupComplete::name(),
+        SettingUpdated::name(),
+    ]
+}
+
+/// 查询可以设置钩子的事件
+#[tauri::command]
+pub fn query_hook_events() -> Vec<&'static str> {
+    hook_events().to_vec()
+}
+
+/// 查询全部钩子
+#[tauri::command]
+pub async fn query_hooks(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<HookVo>, OneClickLaunchError> {
+    let hooks = hook::query_all(&db.pool()).await?;
+    Ok(hooks.into_iter().map(HookVo::from).collect())
+}
+
+/// 新增钩子,未指定超时时间时使用默认值
+#[tauri::command]
+pub async fn create_hook(
+    db: State<'_, DatabaseManager>,
+    event: &str,
+    command: &str,
+    timeout_secs: Option<i64>,
+) -> Result<i64, OneClickLaunchError> {
+    if !hook_events().contains(&event) {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "不支持为该事件设置钩子: {}",
+            event
+        )));
+    }
+    let command = check_command(command)?;
+    let timeout_secs = check_timeout(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS))?;
+
+    let id = hook::create(&db.pool(), event, command, timeout_secs).await?;
+    Ok(id)
+}
+
+/// 修改钩子
+#[tauri::command]
+pub async fn modify_hook(
+    db: State<'_, DatabaseManager>,
+    hook_id: i64,
+    command: &str,
+    timeout_secs: i64,
+    enabled: bool,
+) -> Result<(), OneClickLaunchError> {
+    let command = check_command(command)?;
+    let timeout_secs = check_timeout(timeout_secs)?;
+
+    hook::find_by_id(&db.pool(), hook_id).await?;
+    hook::modify(&db.pool(), hook_id, command, timeout_secs, enabled).await?;
+    Ok(())
+}
+
+/// 删除钩子
+#[tauri::command]
+pub async fn delete_hook(
+    db: State<'_, DatabaseManager>,
+    hook_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    hook::delete(&db.pool(), hook_id).await?;
+    Ok(())
+}
+
+/// 依次执行事件上启用的钩子
+///
+/// 事件载荷以JSON格式写入命令的标准输入,同时通过环境变量传递:
+/// `ONE_CLICK_LAUNCH_EVENT`为事件名称,`ONE_CLICK_LAUNCH_PAYLOAD`为完整载荷,
+/// 载荷的每个字段对应一个`ONE_CLICK_LAUNCH_<字段名大写>`变量。
+/// 单个钩子失败不影响其他钩子,全部执行完成后返回失败的钩子
+pub async fn run_hooks<P: Serialize>(
+    app: AppHandle,
+    event: &'static str,
+    payload: P,
+) -> Result<(), OneClickLaunchError> {
+    let pool = app.state::<DatabaseManager>().pool();
+    let hooks = hook::query_enabled_by_event(&pool, event).await?;
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let payload = serde_json::to_value(payload)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+
+    let mut failures = vec![];
+    for hook in hooks.iter() {
+        if let Err(e) = run_hook(hook, event, &payload).await {
+            warn!("{}", e);
+            failures.push(e.to_string());
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(failures.join("; ")));
+    }
+
+    Ok(())
+}
+
+async fn run_hook(
+    hook: &Hook,
+    event: &str,
+    payload: &serde_json::Value,
+) -> Result<(), OneClickLaunchError> {
+    let payload_json = payload.to_string();
+
+    let mut envs = vec![
+        (format!("{}EVENT", HOOK_ENV_PREFIX), event.to_string()),
+        (format!("{}PAYLOAD", HOOK_ENV_PREFIX), payload_json.clone()),
+    ];
+    if let Some(fields) = payload.as_object() {
+        for (key, value) in fields {
+            let value = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            envs.push((format!("{}{}", HOOK_ENV_PREFIX, key.to_uppercase()), value));
+        }
+    }
+
+    info!("执行钩子 {} ({}): {}", hook.id, event, hook.command);
+
+    let output = run_shell_command(
+        &hook.command,
+        &envs,
+        &payload_json,
+        hook.timeout_secs as u64,
+    )
+    .await
+    .map_err(|e| OneClickLaunchError::ExecutionError(format!("钩子 {} {}", hook.id, e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        info!("钩子 {} 输出: {}", hook.id, stdout.trim());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        warn!("钩子 {} 错误输出: {}", hook.id, stderr.trim());
+    }
+
+    if !output.status.success() {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "钩子 {} 执行失败({}): {}",
+            hook.id, output.status, hook.command
+        )));
+    }
+
+    Ok(())
+}
+
+/// 使用系统shell执行命令并等待结束,`stdin`写入命令的标准输入,超时后结束命令
+pub async fn run_shell_command(
+    command: &str,
+    envs: &[(String, String)],
+    stdin: &str,
+    timeout_secs: u64,
+) -> Result<Output, OneClickLaunchError> {
+    let mut cmd = shell_command(command);
+    cmd.envs(envs.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // 超时后丢弃子进程时结束命令
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn()?;
+
+    if let Some(mut child_stdin) = child.stdin.take() {
+        // 命令不读取标准输入时写入可能失败,忽略该错误
+        let _ = child_stdin.write_all(stdin.as_bytes()).await;
+    }
+
+    let output = timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+        .await
+        .map_err(|_| {
+            OneClickLaunchError::ExecutionError(format!(
+                "执行超时({}秒): {}",
+                timeout_secs, command
+            ))
+        })??;
+
+    Ok(output)
+}
+
+/// 使用系统shell执行命令
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    // 不显示控制台窗口
+    cmd.creation_flags(0x08000000);
+    cmd
+}
+
+/// 使用系统shell执行命令
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+fn check_command(command: &str) -> Result<&str, OneClickLaunchError> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "钩子命令不能为空".to_string(),
+        ));
+    }
+    Ok(command)
+}
+
+fn check_timeout(timeout_secs: i64) -> Result<i64, OneClickLaunchError> {
+    if !(1..=MAX_TIMEOUT_SECS).contains(&timeout_secs) {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "超时时间需要在 1 到 {} 秒之间",
+            MAX_TIMEOUT_SECS
+        )));
+    }
+    Ok(timeout_secs)
+}
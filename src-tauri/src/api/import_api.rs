@@ -0,0 +1,304 @@
+use std::path::Path;
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    DatabaseManager,
+    api::{
+        launcher_api::{self, ResourceParam},
+        undo_api::{self, UndoOperation},
+    },
+    db::{
+        launcher,
+        launcher_resource::{self, CreateResourceParam},
+    },
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{
+            LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, ResourceCreated,
+            ResourceCreatedPayload,
+        },
+    },
+    importer::{
+        bookmarks,
+        desktop_entry::{self, DesktopEntry},
+        recently_used,
+    },
+};
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct DesktopEntryVo {
+    /// 桌面入口文件路径
+    pub file: String,
+    pub name: String,
+    /// 转换后的资源路径
+    pub path: String,
+    pub icon: Option<String>,
+    pub terminal: bool,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct BookmarkFolderVo {
+    pub id: usize,
+    pub parent_id: Option<usize>,
+    pub name: String,
+    /// 从书签根目录开始的文件夹名称路径
+    pub path: Vec<String>,
+    /// 直接位于该文件夹下的书签数量
+    pub bookmark_count: usize,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RecentFileVo {
+    pub path: String,
+    /// 最后一次被使用的时间(Unix秒)
+    pub last_used: i64,
+    /// 使用过该文件的应用程序名称
+    pub applications: Vec<String>,
+}
+
+/// 扫描用户应用程序目录(~/.local/share/applications)中的桌面入口
+#[tauri::command]
+pub async fn scan_desktop_entries() -> Result<Vec<DesktopEntryVo>, OneClickLaunchError> {
+    let Some(dir) = desktop_entry::user_applications_dir() else {
+        return Ok(vec![]);
+    };
+
+    let entries = desktop_entry::scan_dir(&dir)?
+        .into_iter()
+        .filter(|(_, entry)| !entry.hidden)
+        .filter_map(|(file, entry)| {
+            let path = entry.to_resource_path().ok()?;
+            Some(DesktopEntryVo {
+                file: file.to_string_lossy().to_string(),
+                name: entry.name,
+                path,
+                icon: entry.icon,
+                terminal: entry.terminal,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// 把选中的桌面入口文件导入为启动器的资源
+#[tauri::command]
+pub async fn import_desktop_entries(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    files: Vec<String>,
+) -> Result<Vec<i64>, OneClickLaunchError> {
+    let mut resources = Vec::with_capacity(files.len());
+    for file in files.iter() {
+        let entry = DesktopEntry::from_file(Path::new(file))?;
+        resources.push(CreateResourceParam {
+            path: entry.to_resource_path()?,
+            name: entry.name,
+        });
+    }
+
+    let mut tx = db.pool().begin().await?;
+
+    let resource_ids =
+        launcher_resource::create_resources(&mut tx, launcher_id, &resources).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("导入{}个应用程序", resource_ids.len()),
+        UndoOperation::DeleteResources {
+            resource_ids: resource_ids.clone(),
+        },
+    )
+    .await?;
+
+    let resources = launcher_api::query_resource_vos(&mut tx, &resource_ids).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(resource_ids)
+}
+
+/// 解析浏览器导出的书签HTML文件,返回其中的书签文件夹供用户选择
+#[tauri::command]
+pub async fn parse_bookmarks(file: String) -> Result<Vec<BookmarkFolderVo>, OneClickLaunchError> {
+    let folders = bookmarks::parse_file(Path::new(&file))?;
+
+    let folder_vos = folders
+        .iter()
+        .map(|f| BookmarkFolderVo {
+            id: f.id,
+            parent_id: f.parent_id,
+            name: f.name.clone(),
+            path: bookmarks::name_path(&folders, f.id),
+            bookmark_count: f.bookmarks.len(),
+        })
+        .collect();
+
+    Ok(folder_vos)
+}
+
+/// 把选中的书签文件夹导入为启动器,每个文件夹创建一个启动器,书签作为网址资源
+#[tauri::command]
+pub async fn import_bookmarks(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    file: String,
+    folder_ids: Vec<usize>,
+) -> Result<Vec<i64>, OneClickLaunchError> {
+    let folders = bookmarks::parse_file(Path::new(&file))?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let mut launcher_ids = vec![];
+    let mut resource_ids = vec![];
+
+    for folder in folders
+        .iter()
+        .filter(|f| folder_ids.contains(&f.id) && !f.bookmarks.is_empty())
+    {
+        let launcher_id = launcher::create(&mut tx, &folder.name, None).await?;
+
+        for bookmark in folder.bookmarks.iter() {
+            let name = if bookmark.title.is_empty() {
+                &bookmark.url
+            } else {
+                &bookmark.title
+            };
+            resource_ids
+                .push(launcher_resource::create(&mut tx, launcher_id, name, &bookmark.url).await?);
+        }
+
+        launcher_ids.push(launcher_id);
+    }
+
+    for launcher_id in launcher_ids.iter().copied() {
+        undo_api::record(
+            &mut tx,
+            "导入书签",
+            UndoOperation::DeleteLauncher { launcher_id },
+        )
+        .await?;
+    }
+
+    let resources = launcher_api::query_resource_vos(&mut tx, &resource_ids).await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: launcher_ids.clone(),
+        },
+    );
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(launcher_ids)
+}
+
+/// 查询最近使用的文件(~/.local/share/recently-used.xbel),只返回仍然存在的本地文件
+///
+/// - `since`/`until`: 最后使用时间范围(Unix秒)
+/// - `application`: 只返回被该应用程序使用过的文件
+#[tauri::command]
+pub async fn query_recent_files(
+    since: Option<i64>,
+    until: Option<i64>,
+    application: Option<String>,
+) -> Result<Vec<RecentFileVo>, OneClickLaunchError> {
+    let Some(file) = recently_used::default_file().filter(|f| f.exists()) else {
+        return Ok(vec![]);
+    };
+
+    let application = application.filter(|a| !a.is_empty());
+
+    let files = recently_used::parse_file(&file)?
+        .into_iter()
+        .filter(|f| since.is_none_or(|since| f.last_used >= since))
+        .filter(|f| until.is_none_or(|until| f.last_used <= until))
+        .filter(|f| {
+            application
+                .as_ref()
+                .is_none_or(|app| f.applications.iter().any(|a| a == app))
+        })
+        .filter(|f| Path::new(&f.path).exists())
+        .map(|f| RecentFileVo {
+            path: f.path,
+            last_used: f.last_used,
+            applications: f.applications,
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// 使用最近使用的文件创建启动器,返回新启动器的id
+///
+/// `paths`为空时使用`since`/`until`/`application`筛选出的全部文件
+#[tauri::command]
+pub async fn create_launcher_from_recent_files(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    name: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    application: Option<String>,
+    paths: Option<Vec<String>>,
+) -> Result<i64, OneClickLaunchError> {
+    let paths = match paths {
+        Some(paths) => paths,
+        None => query_recent_files(since, until, application)
+            .await?
+            .into_iter()
+            .map(|f| f.path)
+            .collect(),
+    };
+
+    if paths.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "没有符合条件的最近使用文件".to_string(),
+        ));
+    }
+
+    let name = name
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "最近使用的文件".to_string());
+
+    let mut tx = db.pool().begin().await?;
+
+    let launcher_id = launcher::create(&mut tx, &name, None).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("创建启动器: {}", name),
+        UndoOperation::DeleteLauncher { launcher_id },
+    )
+    .await?;
+
+    let resources = paths
+        .into_iter()
+        .map(|path| ResourceParam { name: None, path })
+        .collect();
+    let resource_ids =
+        launcher_api::create_launcher_resources(&mut tx, launcher_id, resources).await?;
+    let resources = launcher_api::query_resource_vos(&mut tx, &resource_ids).await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(launcher_id)
+}
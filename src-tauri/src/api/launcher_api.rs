@@ -1,29 +1,42 @@
-use std::{path::Path, process::Command};
+use std::path::Path;
 
 use anyhow::Result;
 use rand::{Rng, distributions::Alphanumeric};
 use serde::Deserialize;
+use sqlx::{Executor, Sqlite, SqliteConnection, SqlitePool};
 use tauri::{AppHandle, Manager, State};
-use tauri_plugin_opener::OpenerExt;
 use tracing::info;
 
 use crate::{
     DatabaseManager,
-    api::window_api,
+    api::{
+        hook_api,
+        undo_api::{self, UndoOperation},
+        window_api,
+    },
     db::{
-        launcher,
+        folder, launcher,
         launcher_resource::{self, CreateResourceParam, LauncherResource},
+        resource_path_override::{self, ResourcePathOverride},
+        tag,
     },
     error::OneClickLaunchError,
     events::{
         EventDispatcher,
         types::{
             LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, LauncherLaunched,
-            LauncherLaunchedPayload,
+            LauncherLaunchedPayload, ResourceCreated, ResourceCreatedPayload, ResourceDeleted,
+            ResourceDeletedPayload, ResourceUpdated, ResourceUpdatedPayload,
         },
     },
+    importer::desktop_entry::{self, DesktopEntry},
+    paths,
+    resource_handler::ResourceHandlerRegistry,
 };
 
+/// 启动前后命令的超时时间(秒)
+const LAUNCH_COMMAND_TIMEOUT_SECS: u64 = 60;
+
 /// 创建新的启动器
 #[tauri::command]
 pub async fn craete_launcher(
@@ -34,7 +47,10 @@ pub async fn craete_launcher(
     let name = name
         .filter(|s| !s.is_empty())
         .unwrap_or_else(generate_default_launcher_name);
-    let launcher_id = launcher::create(&db.pool, &name, None).await?;
+
+    let mut tx = db.pool().begin().await?;
+    let launcher_id = create_launcher(&mut tx, &name).await?;
+    tx.commit().await?;
 
     let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
         &app,
@@ -46,6 +62,23 @@ pub async fn craete_launcher(
     Ok(launcher_id)
 }
 
+/// 创建启动器并记录撤销操作,返回新启动器的id
+pub async fn create_launcher(
+    conn: &mut SqliteConnection,
+    name: &str,
+) -> Result<i64, OneClickLaunchError> {
+    let launcher_id = launcher::create(&mut *conn, name, None).await?;
+
+    undo_api::record(
+        conn,
+        &format!("创建启动器: {}", name),
+        UndoOperation::DeleteLauncher { launcher_id },
+    )
+    .await?;
+
+    Ok(launcher_id)
+}
+
 fn generate_default_launcher_name() -> String {
     let mut name = "双击编辑".to_string();
     let rad_str = generate_random_string(4);
@@ -61,123 +94,805 @@ fn generate_random_string(length: usize) -> String {
         .collect()
 }
 
-/// 修改启动器名称
+/// 修改启动器名称
+#[tauri::command]
+pub async fn modify_launcher_name(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    name: String,
+) -> Result<(), OneClickLaunchError> {
+    rename_launcher(&db.pool(), launcher_id, &name).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 重命名启动器并记录撤销操作
+pub async fn rename_launcher(
+    pool: &SqlitePool,
+    launcher_id: i64,
+    name: &str,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = pool.begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    launcher::modify_launcher_name(&mut tx, launcher_id, name).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("重命名启动器: {}", current.name),
+        UndoOperation::RenameLauncher {
+            launcher_id,
+            name: current.name.clone(),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// 修改启动器备注
+#[tauri::command]
+pub async fn modify_launcher_notes(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    notes: String,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    launcher::modify_launcher_notes(&mut tx, launcher_id, &notes).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("修改启动器备注: {}", current.name),
+        UndoOperation::ModifyLauncherNotes {
+            launcher_id,
+            notes: current.notes.clone(),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 修改启动器别名,`slug`为空时清除别名
+///
+/// 别名不能包含空白字符,不能是纯数字(避免与id混淆),且不能与其他启动器重复
+#[tauri::command]
+pub async fn modify_launcher_slug(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    slug: Option<String>,
+) -> Result<(), OneClickLaunchError> {
+    let slug = slug.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let launchers = launcher::query(&db.pool()).await?;
+    if let Some(slug) = slug.as_deref() {
+        check_slug(slug)?;
+        if launchers
+            .iter()
+            .any(|l| l.id != launcher_id && l.slug.as_deref() == Some(slug))
+        {
+            return Err(OneClickLaunchError::ExecutionError(format!(
+                "别名已被其他启动器使用: {}",
+                slug
+            )));
+        }
+    }
+
+    launcher::find_by_id(&db.pool(), launcher_id).await?;
+    launcher::modify_launcher_slug(&db.pool(), launcher_id, slug.as_deref()).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+fn check_slug(slug: &str) -> Result<(), OneClickLaunchError> {
+    if slug.chars().any(char::is_whitespace) {
+        return Err(OneClickLaunchError::ExecutionError(
+            "别名不能包含空白字符".to_string(),
+        ));
+    }
+    if slug.parse::<i64>().is_ok() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "别名不能是纯数字".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 根据启动器名称生成别名: 转为小写,空白与符号替换为`-`,与已有别名重复时添加序号
+fn generate_slug(launchers: &[launcher::Launcher], launcher: &launcher::Launcher) -> String {
+    let mut base = String::new();
+    for c in launcher.name.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            base.push(c);
+        } else if !base.is_empty() && !base.ends_with('-') {
+            base.push('-');
+        }
+    }
+    let mut base = base.trim_end_matches('-').to_string();
+    if base.is_empty() || check_slug(&base).is_err() {
+        base = format!("launcher-{}", launcher.id);
+    }
+
+    let taken = |candidate: &str| {
+        launchers
+            .iter()
+            .any(|l| l.id != launcher.id && l.slug.as_deref() == Some(candidate))
+    };
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or(base)
+}
+
+/// 根据id、别名或名称查找启动器,返回启动器id
+///
+/// 依次按id、别名、名称(忽略大小写)精确匹配,都不匹配时按名称或别名的前缀匹配;
+/// 匹配到多个启动器时返回错误并列出这些启动器
+pub async fn resolve_launcher(pool: &SqlitePool, query: &str) -> Result<i64, OneClickLaunchError> {
+    let query = query.trim();
+    let launchers = launcher::query(pool).await?;
+
+    if let Ok(id) = query.parse::<i64>() {
+        if launchers.iter().any(|l| l.id == id) {
+            return Ok(id);
+        }
+    }
+
+    if let Some(l) = launchers.iter().find(|l| l.slug.as_deref() == Some(query)) {
+        return Ok(l.id);
+    }
+
+    let lowercase = query.to_lowercase();
+    let exact = launchers
+        .iter()
+        .filter(|l| l.name.to_lowercase() == lowercase)
+        .collect::<Vec<_>>();
+    let matched = if exact.is_empty() {
+        launchers
+            .iter()
+            .filter(|l| {
+                l.name.to_lowercase().starts_with(&lowercase)
+                    || l.slug
+                        .as_deref()
+                        .is_some_and(|slug| slug.to_lowercase().starts_with(&lowercase))
+            })
+            .collect::<Vec<_>>()
+    } else {
+        exact
+    };
+
+    match matched.as_slice() {
+        [] => Err(OneClickLaunchError::LauncherNotFound(query.to_string())),
+        [l] => Ok(l.id),
+        _ => Err(OneClickLaunchError::AmbiguousLauncher(
+            matched
+                .iter()
+                .map(|l| format!("{} ({})", l.name, l.id))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// 根据id、别名或名称启动启动器
+pub async fn launch_by_query(
+    app: AppHandle,
+    query: &str,
+) -> Result<LaunchReport, OneClickLaunchError> {
+    let pool = app.state::<DatabaseManager>().pool();
+    let launcher_id = resolve_launcher(&pool, query).await?;
+    launch(app, launcher_id).await
+}
+
+/// 通知全部启动器已变更,用于其他进程通过命令行修改了启动器之后刷新
+pub async fn notify_launchers_changed(app: &AppHandle) -> Result<(), OneClickLaunchError> {
+    let pool = app.state::<DatabaseManager>().pool();
+    let launcher_ids = launcher::query(&pool)
+        .await?
+        .into_iter()
+        .map(|l| l.id)
+        .collect();
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        app,
+        LauncherBasicInfoUpdatedPayload { launcher_ids },
+    );
+
+    Ok(())
+}
+
+/// 修改启动器启动前后执行的命令,为空表示不执行
+#[tauri::command]
+pub async fn modify_launcher_launch_commands(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    pre_launch_command: String,
+    post_launch_command: String,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    launcher::modify_launch_commands(
+        &mut tx,
+        launcher_id,
+        pre_launch_command.trim(),
+        post_launch_command.trim(),
+    )
+    .await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("修改启动器启动命令: {}", current.name),
+        UndoOperation::ModifyLaunchCommands {
+            launcher_id,
+            pre_launch_command: current.pre_launch_command.clone(),
+            post_launch_command: current.post_launch_command.clone(),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 复制启动器,包含启动器关联的资源数据
+#[tauri::command]
+pub async fn copy_launcher(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+) -> Result<i64, OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    // 1. 复制启动器
+    let launcher = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    let launcher_resoures = launcher_resource::query_by_launcher_id(&mut tx, launcher_id).await?;
+
+    let new_name = format!("{}-副本", launcher.name);
+
+    // 2. 复制资源
+    let new_launcher_id = launcher::create(&mut tx, &new_name, Some(launcher.sort)).await?;
+
+    // 资源的主机路径一并复制,副本在其他主机上与原启动器行为一致
+    let path_overrides = resource_path_override::query_all(&mut tx).await?;
+    let mut resource_ids = Vec::with_capacity(launcher_resoures.len());
+    for res in launcher_resoures.iter() {
+        let resource_id =
+            launcher_resource::create(&mut tx, new_launcher_id, &res.name, &res.path).await?;
+        for po in path_overrides.iter().filter(|po| po.resource_id == res.id) {
+            resource_path_override::save(&mut tx, resource_id, &po.hostname, &po.path).await?;
+        }
+        resource_ids.push(resource_id);
+    }
+
+    // 3. 复制所属文件夹、备注、启动命令与标签
+    launcher::modify_launcher_folder(&mut tx, new_launcher_id, launcher.folder_id).await?;
+
+    launcher::modify_launcher_notes(&mut tx, new_launcher_id, &launcher.notes).await?;
+
+    launcher::modify_launch_commands(
+        &mut tx,
+        new_launcher_id,
+        &launcher.pre_launch_command,
+        &launcher.post_launch_command,
+    )
+    .await?;
+
+    for lt in tag::query_by_launcher_id(&mut tx, launcher_id).await? {
+        tag::add_launcher_tag(&mut tx, new_launcher_id, lt.tag_id).await?;
+    }
+
+    undo_api::record(
+        &mut tx,
+        &format!("复制启动器: {}", launcher.name),
+        UndoOperation::DeleteLauncher {
+            launcher_id: new_launcher_id,
+        },
+    )
+    .await?;
+
+    let resources = query_resource_vos(&mut tx, &resource_ids).await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![new_launcher_id],
+        },
+    );
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(new_launcher_id)
+}
+
+/// 查询启动器列表
+///
+/// - `folder_id`: 仅返回该文件夹及其子孙文件夹中的启动器
+/// - `tag`: 仅返回带有该标签的启动器
+#[tauri::command]
+pub async fn query_launchers(
+    db: State<'_, DatabaseManager>,
+    folder_id: Option<i64>,
+    tag: Option<String>,
+) -> Result<Vec<LauncherVo>, OneClickLaunchError> {
+    query_launcher_vos(&db.pool(), folder_id, tag).await
+}
+
+/// 查询启动器的视图对象,参数含义同`query_launchers`
+pub async fn query_launcher_vos(
+    pool: &SqlitePool,
+    folder_id: Option<i64>,
+    tag: Option<String>,
+) -> Result<Vec<LauncherVo>, OneClickLaunchError> {
+    let mut launchers = launcher::query(pool).await?;
+
+    let launcher_tags = tag::query_launcher_tags(pool).await?;
+
+    if let Some(folder_id) = folder_id {
+        let folders = folder::query_all(pool).await?;
+        let folder_ids = folder::descendant_ids(&folders, folder_id);
+        launchers.retain(|l| l.folder_id.is_some_and(|id| folder_ids.contains(&id)));
+    }
+
+    if let Some(tag) = tag.filter(|s| !s.is_empty()) {
+        launchers.retain(|l| {
+            launcher_tags
+                .iter()
+                .any(|lt| lt.launcher_id == l.id && lt.name == tag)
+        });
+    }
+
+    let resources = launcher_resource::query_all(pool).await?;
+
+    let path_overrides = resource_path_override::query_all(pool).await?;
+
+    let hostname = tauri_plugin_os::hostname();
+
+    let launcher_vos = launchers
+        .into_iter()
+        .map(|launcher| {
+            let res_vos = resources
+                .iter()
+                .filter(|resource| resource.launcher_id == launcher.id)
+                .map(|resource| to_resource_vo(resource, &path_overrides, &hostname))
+                .collect();
+            let tags = launcher_tags
+                .iter()
+                .filter(|lt| lt.launcher_id == launcher.id)
+                .map(|lt| lt.name.clone())
+                .collect();
+            LauncherVo {
+                id: launcher.id,
+                name: launcher.name,
+                folder_id: launcher.folder_id,
+                notes: launcher.notes,
+                pre_launch_command: launcher.pre_launch_command,
+                post_launch_command: launcher.post_launch_command,
+                slug: launcher.slug,
+                tags,
+                resources: res_vos,
+            }
+        })
+        .collect();
+
+    Ok(launcher_vos)
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct LauncherVo {
+    pub id: i64,
+    pub name: String,
+    pub folder_id: Option<i64>,
+    pub notes: String,
+    /// 启动前执行的命令,命令失败时取消启动
+    pub pre_launch_command: String,
+    /// 启动后执行的命令
+    pub post_launch_command: String,
+    /// 别名,命令行与快捷方式使用别名指定启动器
+    pub slug: Option<String>,
+    pub tags: Vec<String>,
+    pub resources: Vec<LauncherResourceVo>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LauncherResourceVo {
+    pub id: i64,
+    pub launcher_id: i64,
+    pub name: String,
+    pub path: String,
+    /// 路径位于程序所在目录下时可转换成的`$APP_DIR`相对路径,用于提示用户转换
+    pub relative_path: Option<String>,
+    /// 按主机名设置的路径
+    pub path_overrides: Vec<PathOverrideVo>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct PathOverrideVo {
+    pub hostname: String,
+    pub path: String,
+    /// 是否为当前主机的路径,启动时会使用该路径代替资源路径
+    pub active: bool,
+}
+
+fn to_resource_vo(
+    resource: &LauncherResource,
+    path_overrides: &[ResourcePathOverride],
+    hostname: &str,
+) -> LauncherResourceVo {
+    LauncherResourceVo {
+        id: resource.id,
+        launcher_id: resource.launcher_id,
+        name: resource.name.clone(),
+        path: resource.path.clone(),
+        relative_path: paths::to_app_dir_relative(&resource.path),
+        path_overrides: path_overrides
+            .iter()
+            .filter(|o| o.resource_id == resource.id)
+            .map(|o| PathOverrideVo {
+                hostname: o.hostname.clone(),
+                path: o.path.clone(),
+                active: o.hostname.eq_ignore_ascii_case(hostname),
+            })
+            .collect(),
+    }
+}
+
+/// 查询资源的视图对象,作为资源变更事件的载荷
+pub async fn query_resource_vos(
+    conn: &mut SqliteConnection,
+    resource_ids: &[i64],
+) -> Result<Vec<LauncherResourceVo>, OneClickLaunchError> {
+    let resources = launcher_resource::query_by_ids(&mut *conn, resource_ids).await?;
+    let path_overrides = resource_path_override::query_all(&mut *conn).await?;
+    let hostname = tauri_plugin_os::hostname();
+
+    Ok(resources
+        .iter()
+        .map(|resource| to_resource_vo(resource, &path_overrides, &hostname))
+        .collect())
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct FolderVo {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub children: Vec<FolderVo>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TagVo {
+    pub id: i64,
+    pub name: String,
+}
+
+/// 创建文件夹
+#[tauri::command]
+pub async fn create_folder(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    parent_id: Option<i64>,
+    name: String,
+) -> Result<i64, OneClickLaunchError> {
+    if let Some(parent_id) = parent_id {
+        folder::find_by_id(&db.pool(), parent_id).await?;
+    }
+
+    let folder_id = folder::create(&db.pool(), parent_id, &name).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![],
+        },
+    );
+
+    Ok(folder_id)
+}
+
+/// 修改文件夹名称
+#[tauri::command]
+pub async fn modify_folder_name(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    folder_id: i64,
+    name: String,
+) -> Result<(), OneClickLaunchError> {
+    folder::modify_name(&db.pool(), folder_id, &name).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![],
+        },
+    );
+
+    Ok(())
+}
+
+/// 移动文件夹到另一个文件夹下,`parent_id`为空时移动到根目录
+#[tauri::command]
+pub async fn move_folder(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    folder_id: i64,
+    parent_id: Option<i64>,
+) -> Result<(), OneClickLaunchError> {
+    if let Some(parent_id) = parent_id {
+        // 不允许移动到自身或自身的子孙文件夹下,否则会形成环
+        let folders = folder::query_all(&db.pool()).await?;
+        if folder::descendant_ids(&folders, folder_id).contains(&parent_id) {
+            return Err(OneClickLaunchError::ExecutionError(
+                "不能将文件夹移动到其自身或子文件夹中".to_string(),
+            ));
+        }
+    }
+
+    folder::modify_parent(&db.pool(), folder_id, parent_id).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![],
+        },
+    );
+
+    Ok(())
+}
+
+/// 删除文件夹,其中的子文件夹与启动器会移动到上级文件夹
+#[tauri::command]
+pub async fn delete_folder(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    folder_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let deleted = folder::find_by_id(&mut tx, folder_id).await?;
+
+    folder::move_children(&mut tx, folder_id, deleted.parent_id).await?;
+
+    launcher::move_folder_launchers(&mut tx, folder_id, deleted.parent_id).await?;
+
+    folder::delete_by_id(&mut tx, folder_id).await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![],
+        },
+    );
+
+    Ok(())
+}
+
+/// 查询文件夹树
+#[tauri::command]
+pub async fn query_folders(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<FolderVo>, OneClickLaunchError> {
+    let folders = folder::query_all(&db.pool()).await?;
+    Ok(build_folder_tree(&folders, None))
+}
+
+fn build_folder_tree(folders: &[folder::Folder], parent_id: Option<i64>) -> Vec<FolderVo> {
+    folders
+        .iter()
+        .filter(|f| f.parent_id == parent_id)
+        .map(|f| FolderVo {
+            id: f.id,
+            parent_id: f.parent_id,
+            name: f.name.clone(),
+            children: build_folder_tree(folders, Some(f.id)),
+        })
+        .collect()
+}
+
+/// 移动启动器到指定文件夹,`folder_id`为空时移动到根目录
+#[tauri::command]
+pub async fn move_launcher_to_folder(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    folder_id: Option<i64>,
+) -> Result<(), OneClickLaunchError> {
+    if let Some(folder_id) = folder_id {
+        folder::find_by_id(&db.pool(), folder_id).await?;
+    }
+
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    launcher::modify_launcher_folder(&mut tx, launcher_id, folder_id).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("移动启动器: {}", current.name),
+        UndoOperation::MoveLauncher {
+            launcher_id,
+            folder_id: current.folder_id,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 设置启动器的标签,会覆盖启动器原有的标签
+#[tauri::command]
+pub async fn modify_launcher_tags(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+    tags: Vec<String>,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    let current_tags = tag::query_by_launcher_id(&mut tx, launcher_id)
+        .await?
+        .into_iter()
+        .map(|lt| lt.name)
+        .collect();
+
+    tag::delete_by_launcher(&mut tx, launcher_id).await?;
+
+    for name in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let tag_id = tag::get_or_create(&mut tx, name).await?;
+        tag::add_launcher_tag(&mut tx, launcher_id, tag_id).await?;
+    }
+
+    undo_api::record(
+        &mut tx,
+        &format!("修改启动器标签: {}", current.name),
+        UndoOperation::SetLauncherTags {
+            launcher_id,
+            tags: current_tags,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 查询全部标签
+#[tauri::command]
+pub async fn query_tags(db: State<'_, DatabaseManager>) -> Result<Vec<TagVo>, OneClickLaunchError> {
+    let tags = tag::query_all(&db.pool())
+        .await?
+        .into_iter()
+        .map(|t| TagVo {
+            id: t.id,
+            name: t.name,
+        })
+        .collect();
+    Ok(tags)
+}
+
+/// 修改标签名称
 #[tauri::command]
-pub async fn modify_launcher_name(
+pub async fn modify_tag_name(
     app: AppHandle,
     db: State<'_, DatabaseManager>,
-    launcher_id: i64,
+    tag_id: i64,
     name: String,
 ) -> Result<(), OneClickLaunchError> {
-    launcher::modify_launcher_name(&db.pool, launcher_id, &name).await?;
+    tag::modify_name(&db.pool(), tag_id, &name).await?;
 
     let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
         &app,
         LauncherBasicInfoUpdatedPayload {
-            launcher_ids: vec![launcher_id],
+            launcher_ids: vec![],
         },
     );
 
     Ok(())
 }
 
-/// 复制启动器,包含启动器关联的资源数据
+/// 删除标签,同时移除所有启动器上的该标签
 #[tauri::command]
-pub async fn copy_launcher(
+pub async fn delete_tag(
     app: AppHandle,
     db: State<'_, DatabaseManager>,
-    launcher_id: i64,
-) -> Result<i64, OneClickLaunchError> {
-    let mut tx = db.pool.begin().await?;
-
-    // 1. 复制启动器
-    let launcher = launcher::find_by_id(&mut tx, launcher_id).await?;
-
-    let launcher_resoures = launcher_resource::query_by_launcher_id(&mut tx, launcher_id).await?;
-
-    let new_name = format!("{}-副本", launcher.name);
+    tag_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
 
-    // 2. 复制资源
-    let new_launcher_id = launcher::create(&mut tx, &new_name, Some(launcher.sort)).await?;
+    tag::delete_by_tag(&mut tx, tag_id).await?;
 
-    for res in launcher_resoures.iter() {
-        launcher_resource::create(&mut tx, new_launcher_id, &res.name, &res.path).await?;
-    }
+    tag::delete_by_id(&mut tx, tag_id).await?;
 
     tx.commit().await?;
 
     let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
         &app,
         LauncherBasicInfoUpdatedPayload {
-            launcher_ids: vec![launcher_id],
+            launcher_ids: vec![],
         },
     );
 
-    Ok(new_launcher_id)
-}
-
-/// 查询启动器列表
-#[tauri::command]
-pub async fn query_launchers(
-    db: State<'_, DatabaseManager>,
-) -> Result<Vec<LauncherVo>, OneClickLaunchError> {
-    let launchers = launcher::query(&db.pool).await?;
-
-    let resources = launcher_resource::query_all(&db.pool).await?;
-
-    let launcher_vos = launchers
-        .into_iter()
-        .map(|launcher| {
-            let res_vos = resources
-                .iter()
-                .filter(|resource| resource.launcher_id == launcher.id)
-                .map(|resource| LauncherResourceVo {
-                    id: resource.id,
-                    launcher_id: resource.launcher_id,
-                    name: resource.name.clone(),
-                    path: resource.path.clone(),
-                })
-                .collect();
-            LauncherVo {
-                id: launcher.id,
-                name: launcher.name,
-                resources: res_vos,
-            }
-        })
-        .collect();
-
-    Ok(launcher_vos)
-}
-
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
-pub struct LauncherVo {
-    pub id: i64,
-    pub name: String,
-    pub resources: Vec<LauncherResourceVo>,
-}
-
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
-pub struct LauncherResourceVo {
-    pub id: i64,
-    pub launcher_id: i64,
-    pub name: String,
-    pub path: String,
+    Ok(())
 }
 
-/// 删除启动器
+/// 删除启动器,启动器会被移入回收站
 #[tauri::command]
 pub async fn delete_launcher(
     app: AppHandle,
     db: State<'_, DatabaseManager>,
     launcher_id: i64,
 ) -> Result<(), OneClickLaunchError> {
-    let mut tx = db.pool.begin().await?;
-
-    launcher::delete_by_id(&mut tx, launcher_id).await?;
-
-    launcher_resource::delete_by_launcher(&mut tx, launcher_id).await?;
-
-    tx.commit().await?;
+    remove_launcher(&db.pool(), launcher_id).await?;
 
     let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
         &app,
@@ -189,10 +904,33 @@ pub async fn delete_launcher(
     Ok(())
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
+/// 把启动器移入回收站并记录撤销操作
+pub async fn remove_launcher(
+    pool: &SqlitePool,
+    launcher_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = pool.begin().await?;
+
+    let current = launcher::find_by_id(&mut tx, launcher_id).await?;
+
+    launcher::delete_by_id(&mut tx, launcher_id).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("删除启动器: {}", current.name),
+        UndoOperation::RestoreLauncher { launcher_id },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 pub struct LauncherSort {
-    id: i64,
-    sort: i32,
+    pub id: i64,
+    pub sort: i32,
 }
 
 /// 调整启动器顺序
@@ -202,12 +940,26 @@ pub async fn modify_launcher_sort(
     db: State<'_, DatabaseManager>,
     launchers: Vec<LauncherSort>,
 ) -> Result<(), OneClickLaunchError> {
-    let mut tx = db.pool.begin().await?;
+    let mut tx = db.pool().begin().await?;
+
+    let mut current = Vec::with_capacity(launchers.len());
 
     for ls in launchers.iter() {
+        let l = launcher::find_by_id(&mut tx, ls.id).await?;
+        current.push(LauncherSort {
+            id: l.id,
+            sort: l.sort,
+        });
         launcher::modify_launcher_sort(&mut tx, ls.id, ls.sort).await?
     }
 
+    undo_api::record(
+        &mut tx,
+        "调整启动器顺序",
+        UndoOperation::SortLaunchers { launchers: current },
+    )
+    .await?;
+
     tx.commit().await?;
 
     let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
@@ -223,14 +975,33 @@ pub async fn modify_launcher_sort(
 /// 为启动器添加资源
 #[tauri::command]
 pub async fn add_resource(
+    app: AppHandle,
     db: State<'_, DatabaseManager>,
     launcher_id: i64,
     name: Option<String>,
     path: &str,
 ) -> Result<i64, OneClickLaunchError> {
-    let name = name.unwrap_or_else(|| generate_name(path));
+    let CreateResourceParam { name, path } = build_resource_param(name, path.to_string());
+
+    let mut tx = db.pool().begin().await?;
+
+    let resource_id = launcher_resource::create(&mut tx, launcher_id, &name, &path).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("添加资源: {}", name),
+        UndoOperation::DeleteResources {
+            resource_ids: vec![resource_id],
+        },
+    )
+    .await?;
+
+    let resources = query_resource_vos(&mut tx, &[resource_id]).await?;
 
-    let resource_id = launcher_resource::create(&db.pool, launcher_id, &name, path).await?;
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
 
     Ok(resource_id)
 }
@@ -238,24 +1009,48 @@ pub async fn add_resource(
 /// 为启动器添加资源
 #[tauri::command]
 pub async fn add_resources(
+    app: AppHandle,
     db: State<'_, DatabaseManager>,
     launcher_id: i64,
     resources: Vec<ResourceParam>,
 ) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let resource_ids = create_launcher_resources(&mut tx, launcher_id, resources).await?;
+
+    let resources = query_resource_vos(&mut tx, &resource_ids).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(())
+}
+
+/// 批量为启动器添加资源并记录撤销操作,返回新增的资源id
+pub async fn create_launcher_resources(
+    conn: &mut SqliteConnection,
+    launcher_id: i64,
+    resources: Vec<ResourceParam>,
+) -> Result<Vec<i64>, OneClickLaunchError> {
     let crps = resources
         .into_iter()
-        .map(|r| CreateResourceParam {
-            name: r
-                .name
-                .filter(|s| !s.is_empty())
-                .unwrap_or_else(|| generate_name(r.path.as_str())),
-            path: r.path,
-        })
+        .map(|r| build_resource_param(r.name, r.path))
         .collect::<Vec<CreateResourceParam>>();
 
-    launcher_resource::create_resources(&db.pool, launcher_id, &crps).await?;
+    let resource_ids = launcher_resource::create_resources(&mut *conn, launcher_id, &crps).await?;
 
-    Ok(())
+    undo_api::record(
+        conn,
+        &format!("添加{}个资源", resource_ids.len()),
+        UndoOperation::DeleteResources {
+            resource_ids: resource_ids.clone(),
+        },
+    )
+    .await?;
+
+    Ok(resource_ids)
 }
 
 #[derive(Deserialize, Debug)]
@@ -264,12 +1059,40 @@ pub struct ResourceParam {
     pub path: String,
 }
 
+/// 构建新增资源的参数,未指定名称时根据路径生成名称
+///
+/// 桌面入口文件(.desktop)会被解析为可直接执行的命令,名称默认使用入口中的Name
+fn build_resource_param(name: Option<String>, path: String) -> CreateResourceParam {
+    let name = name.filter(|s| !s.is_empty());
+
+    if desktop_entry::is_desktop_entry(&path) {
+        match DesktopEntry::from_file(Path::new(&path))
+            .and_then(|entry| Ok((entry.to_resource_path()?, entry)))
+        {
+            Ok((resource_path, entry)) => {
+                return CreateResourceParam {
+                    name: name.unwrap_or(entry.name),
+                    path: resource_path,
+                };
+            }
+            Err(e) => {
+                tracing::debug!("解析桌面入口文件失败,按普通文件添加: {:?}, {:?}", path, e);
+            }
+        }
+    }
+
+    CreateResourceParam {
+        name: name.unwrap_or_else(|| generate_name(&path)),
+        path,
+    }
+}
+
 fn generate_name(path: &str) -> String {
     if path.starts_with("http") {
         return path.to_string();
     }
 
-    match path.rfind('\\') {
+    match path.rfind(['\\', '/']) {
         Some(index) => path[index + 1..].to_string(),
         None => path.to_string(),
     }
@@ -278,43 +1101,191 @@ fn generate_name(path: &str) -> String {
 /// 修改资源名称
 #[tauri::command]
 pub async fn modify_resource_name(
+    app: AppHandle,
     db: State<'_, DatabaseManager>,
     resource_id: i64,
     name: &str,
 ) -> Result<(), OneClickLaunchError> {
-    launcher_resource::modify_name(&db.pool, resource_id, name).await?;
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher_resource::find_by_id(&mut tx, resource_id).await?;
+
+    launcher_resource::modify_name(&mut tx, resource_id, name).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("重命名资源: {}", current.name),
+        UndoOperation::RenameResource {
+            resource_id,
+            name: current.name.clone(),
+        },
+    )
+    .await?;
+
+    let resources = query_resource_vos(&mut tx, &[resource_id]).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceUpdated>::send_event(&app, ResourceUpdatedPayload { resources });
+
     Ok(())
 }
 
 /// 修改资源路径
 #[tauri::command]
 pub async fn modify_resource_path(
+    app: AppHandle,
     db: State<'_, DatabaseManager>,
     resource_id: i64,
     path: &str,
 ) -> Result<(), OneClickLaunchError> {
-    launcher_resource::modify_path(&db.pool, resource_id, path).await?;
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher_resource::find_by_id(&mut tx, resource_id).await?;
+
+    launcher_resource::modify_path(&mut tx, resource_id, path).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("修改资源路径: {}", current.name),
+        UndoOperation::ModifyResourcePath {
+            resource_id,
+            path: current.path,
+        },
+    )
+    .await?;
+
+    let resources = query_resource_vos(&mut tx, &[resource_id]).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceUpdated>::send_event(&app, ResourceUpdatedPayload { resources });
+
+    Ok(())
+}
+
+/// 设置资源在指定主机上使用的路径,`path`为空时删除该主机的路径
+#[tauri::command]
+pub async fn modify_resource_path_override(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    resource_id: i64,
+    hostname: &str,
+    path: Option<String>,
+) -> Result<(), OneClickLaunchError> {
+    let hostname = hostname.trim();
+    if hostname.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "主机名不能为空".to_string(),
+        ));
+    }
+
+    let mut tx = db.pool().begin().await?;
+
+    let resource = launcher_resource::find_by_id(&mut tx, resource_id).await?;
+    let current = resource_path_override::find(&mut tx, resource_id, hostname).await?;
+
+    match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => resource_path_override::save(&mut tx, resource_id, hostname, path).await?,
+        None => resource_path_override::delete(&mut tx, resource_id, hostname).await?,
+    }
+
+    undo_api::record(
+        &mut tx,
+        &format!("修改资源主机路径: {} ({})", resource.name, hostname),
+        UndoOperation::SetResourcePathOverride {
+            resource_id,
+            hostname: hostname.to_string(),
+            path: current.map(|o| o.path),
+        },
+    )
+    .await?;
+
+    let resources = query_resource_vos(&mut tx, &[resource_id]).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceUpdated>::send_event(&app, ResourceUpdatedPayload { resources });
+
     Ok(())
 }
 
-/// 删除启动器中的资源
+/// 查询当前主机名,用于设置资源在当前主机上的路径
+#[tauri::command]
+pub fn query_hostname() -> String {
+    tauri_plugin_os::hostname()
+}
+
+/// 删除启动器中的资源,资源会被移入回收站
 #[tauri::command]
 pub async fn delete_resource(
+    app: AppHandle,
     db: State<'_, DatabaseManager>,
     resource_id: i64,
 ) -> Result<(), OneClickLaunchError> {
-    launcher_resource::delete_by_id(&db.pool, resource_id).await?;
+    let mut tx = db.pool().begin().await?;
+
+    let current = launcher_resource::find_by_id(&mut tx, resource_id).await?;
+
+    // 删除后无法再查询到资源,需要提前读取事件载荷
+    let resources = query_resource_vos(&mut tx, &[resource_id]).await?;
+
+    launcher_resource::delete_by_id(&mut tx, resource_id).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("删除资源: {}", current.name),
+        UndoOperation::RestoreResources {
+            resource_ids: vec![resource_id],
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceDeleted>::send_event(&app, ResourceDeletedPayload { resources });
+
     Ok(())
 }
 
-/// 启动启动器
+/// 启动启动器,返回启动报告
+///
+/// 设置了启动前命令时先执行该命令,命令失败时取消启动;全部资源启动后执行启动后命令
 #[tauri::command]
-pub async fn launch(app: AppHandle, launcher_id: i64) -> Result<(), OneClickLaunchError> {
+pub async fn launch(app: AppHandle, launcher_id: i64) -> Result<LaunchReport, OneClickLaunchError> {
+    let report = launch_launcher(&app, launcher_id).await?;
+    if report.aborted || report.resources.is_empty() {
+        return Ok(report);
+    }
+
+    EventDispatcher::<LauncherLaunched>::send_event(
+        &app,
+        LauncherLaunchedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    )?;
+
+    Ok(report)
+}
+
+/// 按启动前命令、资源、启动后命令的顺序启动启动器,不发送启动事件
+pub async fn launch_launcher(
+    app: &AppHandle,
+    launcher_id: i64,
+) -> Result<LaunchReport, OneClickLaunchError> {
     let db: State<'_, DatabaseManager> = app.try_state().ok_or(
         OneClickLaunchError::ExecutionError("Unable to get DatabaseManager".to_string()),
     )?;
 
-    let mut resources = launcher_resource::query_by_launcher_id(&db.pool, launcher_id).await?;
+    let launcher = launcher::find_by_id(&db.pool(), launcher_id).await?;
+
+    let mut resources = launcher_resource::query_by_launcher_id(&db.pool(), launcher_id).await?;
+
+    resolve_resource_paths(&db.pool(), &mut resources).await?;
 
     tracing::debug!("启动编组原始资源列表: {resources:?}");
 
@@ -325,42 +1296,201 @@ pub async fn launch(app: AppHandle, launcher_id: i64) -> Result<(), OneClickLaun
         !e.path.starts_with(&app_path)
     });
 
+    let mut report = LaunchReport {
+        launcher_id,
+        pre_launch: None,
+        resources: vec![],
+        post_launch: None,
+        aborted: false,
+    };
+
     if resources.is_empty() {
         tracing::debug!("资源列表为空");
-        return Ok(());
+        return Ok(report);
     }
 
-    launch_resources(&app, &resources);
+    if !launcher.pre_launch_command.is_empty() {
+        let pre_launch = run_launch_command(&launcher, &launcher.pre_launch_command).await;
+        let success = pre_launch.success;
+        report.pre_launch = Some(pre_launch);
+        if !success {
+            info!("启动前命令失败,取消启动: {}", launcher.name);
+            report.aborted = true;
+            return Ok(report);
+        }
+    }
 
-    EventDispatcher::<LauncherLaunched>::send_event(
-        &app,
-        LauncherLaunchedPayload {
-            launcher_ids: vec![launcher_id],
-        },
-    )?;
+    report.resources = launch_resources(app, &resources).await?;
 
-    Ok(())
+    if !launcher.post_launch_command.is_empty() {
+        report.post_launch =
+            Some(run_launch_command(&launcher, &launcher.post_launch_command).await);
+    }
+
+    Ok(report)
+}
+
+/// 启动报告
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LaunchReport {
+    pub launcher_id: i64,
+    /// 启动前命令的执行结果,未设置启动前命令时为空
+    pub pre_launch: Option<CommandReport>,
+    /// 各资源的启动结果
+    pub resources: Vec<ResourceLaunchReport>,
+    /// 启动后命令的执行结果,未设置启动后命令时为空
+    pub post_launch: Option<CommandReport>,
+    /// 是否因启动前命令失败而取消启动
+    pub aborted: bool,
+}
+
+impl LaunchReport {
+    /// 启动前后命令与全部资源是否都执行成功
+    pub fn succeeded(&self) -> bool {
+        !self.aborted
+            && self.resources.iter().all(|r| r.error.is_none())
+            && self.post_launch.as_ref().is_none_or(|r| r.success)
+    }
+}
+
+/// 启动前后命令的执行结果
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CommandReport {
+    pub command: String,
+    pub success: bool,
+    /// 命令的标准输出与错误输出
+    pub output: String,
+    /// 命令无法执行、超时或退出码不为0时的错误信息
+    pub error: Option<String>,
+}
+
+/// 资源的启动结果
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ResourceLaunchReport {
+    pub id: i64,
+    pub name: String,
+    pub path: String,
+    /// 启动失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 执行启动前后的命令,通过环境变量传入启动器id与名称
+async fn run_launch_command(launcher: &launcher::Launcher, command: &str) -> CommandReport {
+    let envs = [
+        (
+            "ONE_CLICK_LAUNCH_LAUNCHER_ID".to_string(),
+            launcher.id.to_string(),
+        ),
+        (
+            "ONE_CLICK_LAUNCH_LAUNCHER_NAME".to_string(),
+            launcher.name.clone(),
+        ),
+    ];
+
+    info!("执行启动器 {} 的命令: {}", launcher.name, command);
+
+    match hook_api::run_shell_command(command, &envs, "", LAUNCH_COMMAND_TIMEOUT_SECS).await {
+        Ok(output) => {
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .trim()
+            .to_string();
+            let error = (!output.status.success())
+                .then(|| format!("命令执行失败({}): {}", output.status, command));
+            CommandReport {
+                command: command.to_string(),
+                success: error.is_none(),
+                output: text,
+                error,
+            }
+        }
+        Err(e) => CommandReport {
+            command: command.to_string(),
+            success: false,
+            output: String::new(),
+            error: Some(e.to_string()),
+        },
+    }
 }
 
-/// 打开路径
+/// 打开路径,脚本资源会执行到结束,因此在阻塞线程中打开
 #[tauri::command]
 pub async fn open_path(app: AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
-    open_using_default_program(&app, path)?;
+    let path = paths::expand_app_dir(path)?;
+    tauri::async_runtime::spawn_blocking(move || open_using_default_program(&app, &path)).await??;
     Ok(())
 }
 
-pub fn launch_resources(app: &AppHandle, resources: &[LauncherResource]) {
-    for resource in resources.iter() {
-        if let Err(e) = open_using_default_program(app, resource.path.as_str()) {
-            info!(
-                "启动资源失败,资源名称: {:?},资源路径: {:?},错误信息: {:?}",
-                &resource.name, &resource.path, e
+/// 解析启动时实际使用的资源路径: 使用当前主机上设置的路径替换资源路径,并展开路径开头的`$APP_DIR`
+pub async fn resolve_resource_paths<'a, E>(
+    executor: E,
+    resources: &mut [LauncherResource],
+) -> Result<(), OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let overrides =
+        resource_path_override::query_by_hostname(executor, &tauri_plugin_os::hostname()).await?;
+
+    for resource in resources.iter_mut() {
+        if let Some(o) = overrides.iter().find(|o| o.resource_id == resource.id) {
+            tracing::debug!(
+                "资源 {} 使用主机 {} 的路径: {}",
+                resource.name,
+                o.hostname,
+                o.path
             );
+            resource.path = o.path.clone();
         }
+        resource.path = paths::expand_app_dir(&resource.path)?;
     }
+
+    Ok(())
+}
+
+/// 依次启动资源,返回各资源的启动结果
+///
+/// 脚本资源会等待执行结束后再启动下一个资源,因此在阻塞线程中启动
+pub async fn launch_resources(
+    app: &AppHandle,
+    resources: &[LauncherResource],
+) -> Result<Vec<ResourceLaunchReport>, OneClickLaunchError> {
+    let app = app.clone();
+    let resources = resources.to_vec();
+    let reports =
+        tauri::async_runtime::spawn_blocking(move || launch_resources_blocking(&app, &resources))
+            .await?;
+    Ok(reports)
 }
 
-/// 使用系统默认的程序打开指定的文件或 URL。
+fn launch_resources_blocking(
+    app: &AppHandle,
+    resources: &[LauncherResource],
+) -> Vec<ResourceLaunchReport> {
+    resources
+        .iter()
+        .map(|resource| {
+            let error = open_using_default_program(app, resource.path.as_str()).err();
+            if let Some(e) = &error {
+                info!(
+                    "启动资源失败,资源名称: {:?},资源路径: {:?},错误信息: {:?}",
+                    &resource.name, &resource.path, e
+                );
+            }
+            ResourceLaunchReport {
+                id: resource.id,
+                name: resource.name.clone(),
+                path: resource.path.clone(),
+                error: error.map(|e| e.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// 使用注册的资源处理器打开指定的文件、程序或 URL。
 ///
 /// # 参数
 /// - `app`: 应用程序状态的引用，用于访问 Tauri 的应用句柄。
@@ -370,59 +1500,65 @@ pub fn launch_resources(app: &AppHandle, resources: &[LauncherResource]) {
 /// - `Ok(())` 表示操作成功。
 /// - `Err(OneClickLaunchError)` 表示操作失败。
 pub fn open_using_default_program(app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
-    match try_open_as_command(path) {
-        Ok(true) => return Ok(()), // 已经作为程序执行
-        Ok(false) => {
-            // 不是程序，交给 opener
-            open_path_with_opener(app, path)?;
-        }
-        Err(e) => {
-            tracing::debug!("作为命令执行失败: {e:?}，尝试默认打开");
-            open_path_with_opener(app, path)?;
-        }
-    }
-
-    Ok(())
+    app.state::<ResourceHandlerRegistry>().launch(app, path)
 }
 
-fn open_path_with_opener(app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
-    app.opener()
-        .open_path(path, None::<&str>)
-        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
-    Ok(())
+/// 检查资源路径: 返回处理该资源的处理器、资源说明以及资源当前是否可以启动
+#[tauri::command]
+pub fn check_resource(
+    registry: State<'_, ResourceHandlerRegistry>,
+    path: &str,
+) -> Result<ResourceCheckVo, OneClickLaunchError> {
+    let path = paths::expand_app_dir(path)?;
+    let handler = registry.matching(&path).into_iter().next().ok_or_else(|| {
+        OneClickLaunchError::ExecutionError(format!("没有能处理该资源的处理器: {}", path))
+    })?;
+
+    Ok(ResourceCheckVo {
+        handler: handler.name().to_string(),
+        description: handler.describe(&path),
+        error: handler.validate(&path).err().map(|e| e.to_string()),
+    })
 }
 
-fn try_open_as_command(path: &str) -> Result<bool, OneClickLaunchError> {
-    let parts = shlex::split(path)
-        .ok_or_else(|| OneClickLaunchError::ExecutionError("无法解析路径".to_string()))?;
-
-    if parts.is_empty() {
-        return Ok(false);
-    }
-
-    let program = &parts[0];
-    let args = &parts[1..];
-
-    // 如果第一个部分是存在的文件（.exe/.bat/.sh 等），才当成命令执行
-    if Path::new(program).exists() {
-        Command::new(program).args(args).spawn()?;
-        return Ok(true);
-    }
-
-    Ok(false)
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct ResourceCheckVo {
+    /// 处理该资源的处理器名称
+    pub handler: String,
+    pub description: String,
+    /// 资源无法启动的原因,可以启动时为空
+    pub error: Option<String>,
 }
 
 #[tauri::command]
 pub async fn create_handler_shortcut(
+    app: AppHandle,
     launcher_id: i64,
     db: State<'_, DatabaseManager>,
 ) -> Result<String, OneClickLaunchError> {
-    let launcher = launcher::find_by_id(&db.pool, launcher_id).await?;
+    let launcher = launcher::find_by_id(&db.pool(), launcher_id).await?;
+
+    // 快捷方式使用别名指定启动器,导出再导入后id变化时快捷方式仍然有效
+    let slug = match launcher.slug.clone() {
+        Some(slug) => slug,
+        None => {
+            let launchers = launcher::query(&db.pool()).await?;
+            let slug = generate_slug(&launchers, &launcher);
+            launcher::modify_launcher_slug(&db.pool(), launcher_id, Some(&slug)).await?;
+            let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+                &app,
+                LauncherBasicInfoUpdatedPayload {
+                    launcher_ids: vec![launcher_id],
+                },
+            );
+            slug
+        }
+    };
 
     let app_path = current_exe_path_str()?;
 
     // 构建参数
-    let args = Some(vec![format!("launch {}", launcher_id)]);
+    let args = Some(vec![format!("launch {}", slug)]);
 
     window_api::create_shortcut(
         &app_path,
@@ -1,3 +1,13 @@
+pub mod backup_api;
+pub mod data_dir_api;
+pub mod hook_api;
+pub mod import_api;
 pub mod launcher_api;
+pub mod profile_api;
+pub mod search_api;
 pub mod setting_api;
+pub mod sync_api;
+pub mod transfer_api;
+pub mod trash_api;
+pub mod undo_api;
 pub mod window_api;
@@ -0,0 +1,201 @@
+use std::{fs, path::PathBuf};
+
+use tauri::{AppHandle, Manager, State};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    DatabaseManager,
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{ProfileSwitched, ProfileSwitchedPayload},
+    },
+    open_database,
+    profile::{self, DEFAULT_PROFILE_ID, Profile},
+};
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct ProfileVo {
+    pub id: String,
+    pub name: String,
+    /// 是否为当前使用的配置
+    pub active: bool,
+}
+
+/// 查询全部配置
+#[tauri::command]
+pub async fn query_profiles(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<ProfileVo>, OneClickLaunchError> {
+    let store = profile::load(&db.data_dir)?;
+    let current = db.profile_id();
+
+    let profiles = store
+        .profiles
+        .into_iter()
+        .map(|p| ProfileVo {
+            active: p.id == current,
+            id: p.id,
+            name: p.name,
+        })
+        .collect();
+
+    Ok(profiles)
+}
+
+/// 新建配置,新配置的数据库在第一次切换到该配置时创建
+#[tauri::command]
+pub async fn create_profile(
+    db: State<'_, DatabaseManager>,
+    name: &str,
+) -> Result<ProfileVo, OneClickLaunchError> {
+    let name = check_name(name)?;
+
+    let mut store = profile::load(&db.data_dir)?;
+    if store.name_taken(name, None) {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "配置已存在: {}",
+            name
+        )));
+    }
+
+    let profile = Profile {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+    };
+    store.profiles.push(profile.clone());
+    profile::save(&db.data_dir, &store)?;
+
+    Ok(ProfileVo {
+        id: profile.id,
+        name: profile.name,
+        active: false,
+    })
+}
+
+/// 修改配置名称
+#[tauri::command]
+pub async fn rename_profile(
+    db: State<'_, DatabaseManager>,
+    profile_id: &str,
+    name: &str,
+) -> Result<(), OneClickLaunchError> {
+    let name = check_name(name)?;
+
+    let mut store = profile::load(&db.data_dir)?;
+    if store.name_taken(name, Some(profile_id)) {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "配置已存在: {}",
+            name
+        )));
+    }
+
+    let profile = store
+        .profiles
+        .iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| not_found(profile_id))?;
+    profile.name = name.to_string();
+    profile::save(&db.data_dir, &store)?;
+
+    Ok(())
+}
+
+/// 删除配置及其数据库,不能删除默认配置与当前使用的配置
+#[tauri::command]
+pub async fn delete_profile(
+    db: State<'_, DatabaseManager>,
+    profile_id: &str,
+) -> Result<(), OneClickLaunchError> {
+    if profile_id == DEFAULT_PROFILE_ID {
+        return Err(OneClickLaunchError::ExecutionError(
+            "不能删除默认配置".to_string(),
+        ));
+    }
+    if profile_id == db.profile_id() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "不能删除当前使用的配置".to_string(),
+        ));
+    }
+
+    let mut store = profile::load(&db.data_dir)?;
+    if !store.profiles.iter().any(|p| p.id == profile_id) {
+        return Err(not_found(profile_id));
+    }
+    store.profiles.retain(|p| p.id != profile_id);
+    if store.active == profile_id {
+        store.active = DEFAULT_PROFILE_ID.to_string();
+    }
+    profile::save(&db.data_dir, &store)?;
+
+    // 备份文件保留,以便误删后恢复
+    let db_path = profile::db_path(&db.data_dir, profile_id);
+    for suffix in ["", "-wal", "-shm"] {
+        let mut path = db_path.as_os_str().to_owned();
+        path.push(suffix);
+        let path = PathBuf::from(path);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    info!("已删除配置: {}", profile_id);
+
+    Ok(())
+}
+
+/// 切换配置
+#[tauri::command]
+pub async fn switch_profile(app: AppHandle, profile_id: String) -> Result<(), OneClickLaunchError> {
+    switch_to(&app, &profile_id).await
+}
+
+/// 切换到指定id或名称的配置,并记录为下次启动时使用的配置
+pub async fn switch_to(app: &AppHandle, id_or_name: &str) -> Result<(), OneClickLaunchError> {
+    let db = app.state::<DatabaseManager>();
+
+    let mut store = profile::load(&db.data_dir)?;
+    let profile = store
+        .find(id_or_name)
+        .cloned()
+        .ok_or_else(|| not_found(id_or_name))?;
+
+    if profile.id == db.profile_id() {
+        return Ok(());
+    }
+
+    let db_path = profile::db_path(&db.data_dir, &profile.id);
+    let pool = open_database(&db_path, true).await?;
+
+    // 原来的连接池可能仍被正在执行的操作持有,不主动关闭,全部引用释放后自动关闭
+    drop(db.replace(pool, db_path, profile.id.clone()));
+
+    store.active = profile.id.clone();
+    profile::save(&db.data_dir, &store)?;
+
+    info!("已切换到配置: {} ({})", profile.name, profile.id);
+
+    EventDispatcher::<ProfileSwitched>::send_event(
+        app,
+        ProfileSwitchedPayload {
+            profile_id: profile.id,
+        },
+    )?;
+
+    Ok(())
+}
+
+fn check_name(name: &str) -> Result<&str, OneClickLaunchError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(
+            "配置名称不能为空".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+fn not_found(profile_id: &str) -> OneClickLaunchError {
+    OneClickLaunchError::ExecutionError(format!("配置不存在: {}", profile_id))
+}
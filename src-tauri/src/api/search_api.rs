@@ -0,0 +1,66 @@
+use tauri::State;
+
+use crate::{DatabaseManager, db::search_index, error::OneClickLaunchError};
+
+/// 默认返回的最大命中数量
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct SearchHitVo {
+    /// 命中的记录类型: launcher / resource
+    pub kind: String,
+    /// 启动器id或资源id
+    pub id: i64,
+    pub launcher_id: i64,
+    /// 以下字段中命中的内容使用`<mark>`标签包裹
+    pub name: String,
+    pub path: String,
+    pub tags: String,
+    pub notes: String,
+    pub rank: f64,
+}
+
+/// 按名称、路径(含启动参数)、标签与备注检索启动器和资源,结果按相关度排序
+#[tauri::command]
+pub async fn search(
+    db: State<'_, DatabaseManager>,
+    keyword: String,
+    limit: Option<i64>,
+) -> Result<Vec<SearchHitVo>, OneClickLaunchError> {
+    let Some(query) = build_match_query(&keyword) else {
+        return Ok(vec![]);
+    };
+
+    let hits = search_index::search(&db.pool(), &query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .await?
+        .into_iter()
+        .map(|hit| SearchHitVo {
+            kind: hit.kind,
+            id: hit.item_id,
+            launcher_id: hit.launcher_id,
+            name: hit.name,
+            path: hit.path,
+            tags: hit.tags,
+            notes: hit.notes,
+            rank: hit.rank,
+        })
+        .collect();
+
+    Ok(hits)
+}
+
+/// 把用户输入转换为FTS5查询表达式: 每个关键字都作为前缀匹配,关键字之间为"与"关系
+///
+/// 关键字会被双引号包裹,避免用户输入中的FTS5语法字符导致查询报错
+fn build_match_query(keyword: &str) -> Option<String> {
+    let terms = keyword
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<String>>();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
@@ -3,15 +3,29 @@ use tauri::{AppHandle, State};
 
 use crate::{
     DatabaseManager,
+    constants::LAUNCH_THEN_EXIT_KEY,
     db::settings::{self, Settings},
     error::OneClickLaunchError,
     events::{
         EventDispatcher,
         types::{SettingUpdated, SettingUpdatedPayload},
     },
+    setting_registry::{self, SETTING_DEFINITIONS, SettingKind, SettingValue},
 };
 
-/// 保存设置
+/// 设置项及其类型化的值
+#[derive(Debug, serde::Serialize)]
+pub struct TypedSettingVo {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub value: SettingValue,
+    pub default: SettingValue,
+    /// 是否尚未保存过该设置项或保存的值无效,此时`value`为默认值
+    pub is_default: bool,
+    pub description: &'static str,
+}
+
+/// 保存设置,取值不符合设置项类型时返回错误
 #[tauri::command]
 pub async fn save_setting(
     app: AppHandle,
@@ -19,8 +33,9 @@ pub async fn save_setting(
     key: String,
     value: String,
 ) -> Result<(), OneClickLaunchError> {
+    let value = setting_registry::validate(&key, &value)?;
     let setting = Settings { key, value };
-    settings::save(&db.pool, &setting).await?;
+    settings::save(&db.pool(), &setting).await?;
 
     let _ = EventDispatcher::<SettingUpdated>::send_event(
         &app,
@@ -39,7 +54,7 @@ pub async fn read_setting(
     db: State<'_, DatabaseManager>,
     key: &str,
 ) -> Result<Option<Settings>, OneClickLaunchError> {
-    let setting = settings::read(&db.pool, key).await?;
+    let setting = settings::read(&db.pool(), key).await?;
     Ok(setting)
 }
 
@@ -48,20 +63,42 @@ pub async fn read_setting(
 pub async fn read_all_setting(
     db: State<'_, DatabaseManager>,
 ) -> Result<Vec<Settings>, OneClickLaunchError> {
-    let setting = settings::read_all(&db.pool).await?;
+    let setting = settings::read_all(&db.pool()).await?;
     Ok(setting)
 }
 
+/// 读取全部已知设置项的类型化值,未保存的设置项返回默认值
+#[tauri::command]
+pub async fn read_all_typed_setting(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<TypedSettingVo>, OneClickLaunchError> {
+    let saved = settings::read_all(&db.pool()).await?;
+
+    let typed_settings = SETTING_DEFINITIONS
+        .iter()
+        .map(|definition| {
+            let default = definition.default_value();
+            let value = saved
+                .iter()
+                .find(|s| s.key == definition.key)
+                .and_then(|s| definition.parse(&s.value).ok());
+            TypedSettingVo {
+                key: definition.key,
+                kind: definition.kind,
+                is_default: value.is_none(),
+                value: value.unwrap_or_else(|| default.clone()),
+                default,
+                description: definition.description,
+            }
+        })
+        .collect();
+
+    Ok(typed_settings)
+}
+
 pub async fn check_launch_then_exit<'a, E>(executor: E) -> Result<bool, OneClickLaunchError>
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    match settings::read(executor, "launch_then_exit").await {
-        Ok(Some(setting)) => Ok(string_to_bool(&setting.value)),
-        _ => Ok(false),
-    }
-}
-
-pub fn string_to_bool(s: &str) -> bool {
-    matches!(s.trim().to_lowercase().as_str(), "true")
+    setting_registry::read_bool(executor, LAUNCH_THEN_EXIT_KEY).await
 }
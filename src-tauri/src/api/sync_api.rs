@@ -0,0 +1,792 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use tauri::{AppHandle, State};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::{
+    DatabaseManager,
+    api::launcher_api::{self, LauncherResourceVo},
+    constants::{SYNC_FOLDER_KEY, SYNC_MACHINE_ID_KEY},
+    db::{
+        folder, launcher, launcher_resource, resource_path_override,
+        settings::{self, Settings},
+        sync::{self, LAUNCHER_KIND, RESOURCE_KIND, Tombstone},
+        tag,
+    },
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{
+            LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, ResourceCreated,
+            ResourceCreatedPayload, ResourceDeleted, ResourceDeletedPayload, ResourceUpdated,
+            ResourceUpdatedPayload,
+        },
+    },
+    setting_registry,
+};
+
+/// 同步文件格式版本,格式发生不兼容变更时递增
+pub const SYNC_FORMAT_VERSION: u32 = 1;
+
+/// 同步文件,每台设备在同步文件夹中写入一个`<machine_id>.json`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SyncSnapshot {
+    pub version: u32,
+    pub machine_id: String,
+    #[serde(default)]
+    pub hostname: String,
+    #[serde(default)]
+    pub launchers: Vec<SyncedLauncher>,
+    #[serde(default)]
+    pub resources: Vec<SyncedResource>,
+    #[serde(default)]
+    pub tombstones: Vec<SyncedTombstone>,
+}
+
+/// 同步的启动器
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedLauncher {
+    pub uuid: String,
+    pub name: String,
+    pub sort: i32,
+    /// 从根目录开始的文件夹名称路径,为空表示位于根目录
+    pub folder: Vec<String>,
+    pub notes: String,
+    pub tags: Vec<String>,
+    /// 启动前执行的命令
+    #[serde(default)]
+    pub pre_launch_command: String,
+    /// 启动后执行的命令
+    #[serde(default)]
+    pub post_launch_command: String,
+    /// 别名
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// 修改时间(Unix毫秒)
+    pub updated_at: i64,
+    /// 移入回收站的时间(Unix秒)
+    pub deleted_at: Option<i64>,
+}
+
+/// 同步的资源
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedResource {
+    pub uuid: String,
+    pub launcher_uuid: String,
+    pub name: String,
+    pub path: String,
+    /// 按主机名设置的路径
+    #[serde(default)]
+    pub path_overrides: Vec<SyncedPathOverride>,
+    /// 修改时间(Unix毫秒)
+    pub updated_at: i64,
+    /// 移入回收站的时间(Unix秒)
+    pub deleted_at: Option<i64>,
+}
+
+/// 同步的资源主机路径
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedPathOverride {
+    pub hostname: String,
+    pub path: String,
+}
+
+/// 已彻底删除的记录
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncedTombstone {
+    pub uuid: String,
+    pub kind: String,
+    /// 删除时间(Unix毫秒)
+    pub deleted_at: i64,
+}
+
+impl SyncedLauncher {
+    /// 除修改时间外内容是否相同
+    fn same_content(&self, other: &Self) -> bool {
+        Self {
+            updated_at: 0,
+            ..self.clone()
+        } == Self {
+            updated_at: 0,
+            ..other.clone()
+        }
+    }
+}
+
+impl SyncedResource {
+    /// 除修改时间外内容是否相同
+    fn same_content(&self, other: &Self) -> bool {
+        Self {
+            updated_at: 0,
+            ..self.clone()
+        } == Self {
+            updated_at: 0,
+            ..other.clone()
+        }
+    }
+}
+
+/// 同步结果
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SyncReportVo {
+    /// 是否开启了同步
+    pub enabled: bool,
+    /// 本机同步文件是否有更新
+    pub exported: bool,
+    /// 本次导入的设备
+    pub imported_machines: Vec<String>,
+    /// 因导入发生变更的启动器id
+    pub updated_launcher_ids: Vec<i64>,
+    /// 本次新增的冲突数量
+    pub conflicts: usize,
+}
+
+/// 同步冲突
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SyncConflictVo {
+    pub id: i64,
+    pub kind: String,
+    pub uuid: String,
+    /// 冲突时本机的记录(JSON)
+    pub local_value: String,
+    /// 冲突时其他设备的记录(JSON)
+    pub remote_value: String,
+    /// 最终保留的一方: local 或 remote
+    pub winner: String,
+    pub remote_machine: String,
+    /// 发生冲突的时间(Unix毫秒)
+    pub created_at: i64,
+}
+
+/// 导入单个同步文件的结果
+#[derive(Debug, Default)]
+struct MergeOutcome {
+    launcher_ids: Vec<i64>,
+    /// 新建的资源id
+    created_resource_ids: Vec<i64>,
+    /// 被覆盖的资源id
+    updated_resource_ids: Vec<i64>,
+    /// 彻底删除的资源,需要在删除前读取
+    deleted_resources: Vec<LauncherResourceVo>,
+    conflicts: usize,
+}
+
+/// 立即执行一次同步
+#[tauri::command]
+pub async fn sync_now(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+) -> Result<SyncReportVo, OneClickLaunchError> {
+    run_sync(&app, &db.pool()).await
+}
+
+/// 查询同步冲突记录
+#[tauri::command]
+pub async fn query_sync_conflicts(
+    db: State<'_, DatabaseManager>,
+) -> Result<Vec<SyncConflictVo>, OneClickLaunchError> {
+    let conflicts = sync::query_conflicts(&db.pool())
+        .await?
+        .into_iter()
+        .map(|c| SyncConflictVo {
+            id: c.id,
+            kind: c.kind,
+            uuid: c.uuid,
+            local_value: c.local_value,
+            remote_value: c.remote_value,
+            winner: c.winner,
+            remote_machine: c.remote_machine,
+            created_at: c.created_at,
+        })
+        .collect();
+
+    Ok(conflicts)
+}
+
+/// 清空同步冲突记录
+#[tauri::command]
+pub async fn clear_sync_conflicts(
+    db: State<'_, DatabaseManager>,
+) -> Result<(), OneClickLaunchError> {
+    sync::clear_conflicts(&db.pool()).await?;
+    Ok(())
+}
+
+/// 导入同步文件夹中其他设备有更新的同步文件,再写出本机的同步文件
+pub async fn run_sync(
+    app: &AppHandle,
+    pool: &SqlitePool,
+) -> Result<SyncReportVo, OneClickLaunchError> {
+    let Some(sync_dir) = read_sync_dir(pool).await? else {
+        return Ok(SyncReportVo::default());
+    };
+
+    fs::create_dir_all(&sync_dir)?;
+
+    let machine_id = read_machine_id(pool).await?;
+
+    let mut report = SyncReportVo {
+        enabled: true,
+        ..Default::default()
+    };
+    let mut created_resource_ids = vec![];
+    let mut updated_resource_ids = vec![];
+    let mut deleted_resources = vec![];
+
+    for entry in fs::read_dir(&sync_dir)? {
+        let path = entry?.path();
+
+        let is_snapshot = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if !is_snapshot
+            || path
+                .file_stem()
+                .is_some_and(|stem| stem == machine_id.as_str())
+        {
+            continue;
+        }
+
+        match import_snapshot_file(pool, &path, &machine_id).await {
+            Ok(Some((remote_machine, outcome))) => {
+                report.imported_machines.push(remote_machine);
+                report.updated_launcher_ids.extend(outcome.launcher_ids);
+                created_resource_ids.extend(outcome.created_resource_ids);
+                updated_resource_ids.extend(outcome.updated_resource_ids);
+                deleted_resources.extend(outcome.deleted_resources);
+                report.conflicts += outcome.conflicts;
+            }
+            Ok(None) => {}
+            Err(e) => warn!("导入同步文件失败: {:?}, {:?}", path, e),
+        }
+    }
+
+    report.updated_launcher_ids.sort_unstable();
+    report.updated_launcher_ids.dedup();
+
+    report.exported = write_snapshot(pool, &sync_dir, &machine_id).await?;
+
+    if !report.updated_launcher_ids.is_empty() {
+        let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+            app,
+            LauncherBasicInfoUpdatedPayload {
+                launcher_ids: report.updated_launcher_ids.clone(),
+            },
+        );
+    }
+
+    if !created_resource_ids.is_empty() || !updated_resource_ids.is_empty() {
+        let mut conn = pool.acquire().await?;
+        let created = launcher_api::query_resource_vos(&mut conn, &created_resource_ids).await?;
+        // 同一资源可能先被创建再被其他设备的同步文件覆盖,只发送创建事件
+        updated_resource_ids.retain(|id| !created_resource_ids.contains(id));
+        let updated = launcher_api::query_resource_vos(&mut conn, &updated_resource_ids).await?;
+        if !created.is_empty() {
+            let _ = EventDispatcher::<ResourceCreated>::send_event(
+                app,
+                ResourceCreatedPayload { resources: created },
+            );
+        }
+        if !updated.is_empty() {
+            let _ = EventDispatcher::<ResourceUpdated>::send_event(
+                app,
+                ResourceUpdatedPayload { resources: updated },
+            );
+        }
+    }
+    if !deleted_resources.is_empty() {
+        let _ = EventDispatcher::<ResourceDeleted>::send_event(
+            app,
+            ResourceDeletedPayload {
+                resources: deleted_resources,
+            },
+        );
+    }
+
+    debug!("run_sync 同步完成: {:?}", report);
+
+    Ok(report)
+}
+
+/// 写出本机的同步文件,未开启同步或内容没有变化时不写入
+pub async fn export_snapshot(pool: &SqlitePool) -> Result<bool, OneClickLaunchError> {
+    let Some(sync_dir) = read_sync_dir(pool).await? else {
+        return Ok(false);
+    };
+
+    fs::create_dir_all(&sync_dir)?;
+
+    let machine_id = read_machine_id(pool).await?;
+
+    write_snapshot(pool, &sync_dir, &machine_id).await
+}
+
+async fn write_snapshot(
+    pool: &SqlitePool,
+    sync_dir: &Path,
+    machine_id: &str,
+) -> Result<bool, OneClickLaunchError> {
+    let mut conn = pool.acquire().await?;
+
+    let snapshot = SyncSnapshot {
+        version: SYNC_FORMAT_VERSION,
+        machine_id: machine_id.to_string(),
+        hostname: tauri_plugin_os::hostname(),
+        launchers: query_synced_launchers(&mut conn)
+            .await?
+            .into_iter()
+            .map(|(_, l)| l)
+            .collect(),
+        resources: query_synced_resources(&mut conn)
+            .await?
+            .into_iter()
+            .map(|(_, r)| r)
+            .collect(),
+        tombstones: sync::query_tombstones(&mut conn)
+            .await?
+            .into_iter()
+            .map(|t| SyncedTombstone {
+                uuid: t.uuid,
+                kind: t.kind,
+                deleted_at: t.deleted_at,
+            })
+            .collect(),
+    };
+
+    let content = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+
+    let path = sync_dir.join(format!("{}.json", machine_id));
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+
+    // 先写入临时文件再重命名,避免其他设备读到写了一半的文件
+    let tmp_path = sync_dir.join(format!("{}.json.tmp", machine_id));
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(true)
+}
+
+/// 导入其他设备的同步文件,文件自上次导入后没有变化时返回None
+async fn import_snapshot_file(
+    pool: &SqlitePool,
+    path: &Path,
+    machine_id: &str,
+) -> Result<Option<(String, MergeOutcome)>, OneClickLaunchError> {
+    let modified_at = fs::metadata(path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default();
+
+    let snapshot: SyncSnapshot = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+
+    if snapshot.version > SYNC_FORMAT_VERSION {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "同步文件版本({})高于当前支持的版本({})",
+            snapshot.version, SYNC_FORMAT_VERSION
+        )));
+    }
+
+    if snapshot.machine_id == machine_id {
+        return Ok(None);
+    }
+
+    let state = sync::find_state(pool, &snapshot.machine_id).await?;
+    if state.is_some_and(|(file_modified_at, _)| file_modified_at >= modified_at) {
+        return Ok(None);
+    }
+    let last_imported_at = state.map(|(_, imported_at)| imported_at).unwrap_or(0);
+
+    let remote_machine = if snapshot.hostname.is_empty() {
+        snapshot.machine_id.clone()
+    } else {
+        snapshot.hostname.clone()
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let outcome = merge_snapshot(&mut tx, &snapshot, &remote_machine, last_imported_at).await?;
+
+    sync::save_state(&mut tx, &snapshot.machine_id, modified_at, now_millis()).await?;
+
+    tx.commit().await?;
+
+    Ok(Some((remote_machine, outcome)))
+}
+
+/// 按最后修改者优先的规则合并其他设备的同步文件
+///
+/// 双方自上次导入后都修改了同一条记录时,记录一条冲突
+async fn merge_snapshot(
+    conn: &mut SqliteConnection,
+    snapshot: &SyncSnapshot,
+    remote_machine: &str,
+    last_imported_at: i64,
+) -> Result<MergeOutcome, OneClickLaunchError> {
+    let mut outcome = MergeOutcome::default();
+
+    let tombstones = sync::query_tombstones(&mut *conn).await?;
+    let is_buried = |uuid: &str, updated_at: i64| {
+        tombstones
+            .iter()
+            .any(|t| t.uuid == uuid && t.deleted_at >= updated_at)
+    };
+
+    // 启动器
+    let local_launchers = query_synced_launchers(&mut *conn).await?;
+    for remote in snapshot.launchers.iter() {
+        match local_launchers.iter().find(|(_, l)| l.uuid == remote.uuid) {
+            Some((launcher_id, local)) => {
+                if local.same_content(remote) {
+                    continue;
+                }
+
+                let remote_wins = remote.updated_at > local.updated_at;
+
+                if local.updated_at > last_imported_at && remote.updated_at > last_imported_at {
+                    record_conflict(
+                        conn,
+                        LAUNCHER_KIND,
+                        &remote.uuid,
+                        local,
+                        remote,
+                        remote_wins,
+                        remote_machine,
+                    )
+                    .await?;
+                    outcome.conflicts += 1;
+                }
+
+                if remote_wins {
+                    apply_launcher(conn, *launcher_id, remote).await?;
+                    outcome.launcher_ids.push(*launcher_id);
+                }
+            }
+            None if is_buried(&remote.uuid, remote.updated_at) => {}
+            None => {
+                let launcher_id =
+                    launcher::create(&mut *conn, &remote.name, Some(remote.sort)).await?;
+                sync::modify_uuid(&mut *conn, LAUNCHER_KIND, launcher_id, &remote.uuid).await?;
+                apply_launcher(conn, launcher_id, remote).await?;
+                outcome.launcher_ids.push(launcher_id);
+            }
+        }
+    }
+
+    // 资源,所属启动器需要已经存在
+    let launcher_ids = sync::query_launchers(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|l| (l.uuid, l.id))
+        .collect::<HashMap<String, i64>>();
+    let local_resources = query_synced_resources(&mut *conn).await?;
+    for remote in snapshot.resources.iter() {
+        let Some(launcher_id) = launcher_ids.get(&remote.launcher_uuid).copied() else {
+            continue;
+        };
+
+        match local_resources.iter().find(|(_, r)| r.uuid == remote.uuid) {
+            Some((resource_id, local)) => {
+                if local.same_content(remote) {
+                    continue;
+                }
+
+                let remote_wins = remote.updated_at > local.updated_at;
+
+                if local.updated_at > last_imported_at && remote.updated_at > last_imported_at {
+                    record_conflict(
+                        conn,
+                        RESOURCE_KIND,
+                        &remote.uuid,
+                        local,
+                        remote,
+                        remote_wins,
+                        remote_machine,
+                    )
+                    .await?;
+                    outcome.conflicts += 1;
+                }
+
+                if remote_wins {
+                    apply_resource(conn, *resource_id, remote).await?;
+                    outcome.launcher_ids.push(launcher_id);
+                    outcome.updated_resource_ids.push(*resource_id);
+                }
+            }
+            None if is_buried(&remote.uuid, remote.updated_at) => {}
+            None => {
+                let resource_id =
+                    launcher_resource::create(&mut *conn, launcher_id, &remote.name, &remote.path)
+                        .await?;
+                sync::modify_uuid(&mut *conn, RESOURCE_KIND, resource_id, &remote.uuid).await?;
+                apply_resource(conn, resource_id, remote).await?;
+                outcome.launcher_ids.push(launcher_id);
+                outcome.created_resource_ids.push(resource_id);
+            }
+        }
+    }
+
+    // 彻底删除,只删除在删除之后没有再修改过的记录
+    let local_launchers = sync::query_launchers(&mut *conn).await?;
+    let local_resources = sync::query_resources(&mut *conn).await?;
+    for tombstone in snapshot.tombstones.iter() {
+        if tombstone.kind == LAUNCHER_KIND {
+            if let Some(local) = local_launchers
+                .iter()
+                .find(|l| l.uuid == tombstone.uuid && l.updated_at <= tombstone.deleted_at)
+            {
+                launcher::delete_by_id(&mut *conn, local.id).await?;
+                launcher::purge_by_id(&mut *conn, local.id).await?;
+                launcher_resource::delete_by_launcher(&mut *conn, local.id).await?;
+                tag::delete_by_launcher(&mut *conn, local.id).await?;
+                outcome.launcher_ids.push(local.id);
+            }
+        } else if let Some(local) = local_resources
+            .iter()
+            .find(|r| r.uuid == tombstone.uuid && r.updated_at <= tombstone.deleted_at)
+        {
+            outcome
+                .deleted_resources
+                .extend(launcher_api::query_resource_vos(&mut *conn, &[local.id]).await?);
+            launcher_resource::delete_by_id(&mut *conn, local.id).await?;
+            launcher_resource::purge_by_id(&mut *conn, local.id).await?;
+            outcome.launcher_ids.push(local.launcher_id);
+        }
+
+        sync::save_tombstone(
+            &mut *conn,
+            &Tombstone {
+                uuid: tombstone.uuid.clone(),
+                kind: tombstone.kind.clone(),
+                deleted_at: tombstone.deleted_at,
+            },
+        )
+        .await?;
+    }
+
+    Ok(outcome)
+}
+
+/// 使用其他设备的启动器内容覆盖本机启动器
+async fn apply_launcher(
+    conn: &mut SqliteConnection,
+    launcher_id: i64,
+    remote: &SyncedLauncher,
+) -> Result<(), OneClickLaunchError> {
+    launcher::modify_launcher_name(&mut *conn, launcher_id, &remote.name).await?;
+    launcher::modify_launcher_sort(&mut *conn, launcher_id, remote.sort).await?;
+    launcher::modify_launcher_notes(&mut *conn, launcher_id, &remote.notes).await?;
+    launcher::modify_launch_commands(
+        &mut *conn,
+        launcher_id,
+        &remote.pre_launch_command,
+        &remote.post_launch_command,
+    )
+    .await?;
+    launcher::modify_launcher_slug(&mut *conn, launcher_id, remote.slug.as_deref()).await?;
+
+    // 按名称路径查找文件夹,不存在时创建
+    let mut folder_id = None;
+    for folder_name in remote.folder.iter() {
+        folder_id = match folder::find_child(&mut *conn, folder_id, folder_name).await? {
+            Some(f) => Some(f.id),
+            None => Some(folder::create(&mut *conn, folder_id, folder_name).await?),
+        };
+    }
+    launcher::modify_launcher_folder(&mut *conn, launcher_id, folder_id).await?;
+
+    tag::delete_by_launcher(&mut *conn, launcher_id).await?;
+    for tag_name in remote.tags.iter() {
+        let tag_id = tag::get_or_create(&mut *conn, tag_name).await?;
+        tag::add_launcher_tag(&mut *conn, launcher_id, tag_id).await?;
+    }
+
+    sync::modify_deleted_at(&mut *conn, LAUNCHER_KIND, launcher_id, remote.deleted_at).await?;
+    // 别名可能已被本机的其他启动器使用,此时清除同步过来的别名
+    launcher::clear_duplicate_slug(&mut *conn, launcher_id).await?;
+
+    // 最后写入修改时间,保持与其他设备一致,避免互相覆盖
+    sync::modify_updated_at(&mut *conn, LAUNCHER_KIND, launcher_id, remote.updated_at).await?;
+
+    Ok(())
+}
+
+/// 使用其他设备的资源内容覆盖本机资源
+async fn apply_resource(
+    conn: &mut SqliteConnection,
+    resource_id: i64,
+    remote: &SyncedResource,
+) -> Result<(), OneClickLaunchError> {
+    launcher_resource::modify_name(&mut *conn, resource_id, &remote.name).await?;
+    launcher_resource::modify_path(&mut *conn, resource_id, &remote.path).await?;
+
+    resource_path_override::delete_by_resource(&mut *conn, resource_id).await?;
+    for path_override in remote.path_overrides.iter() {
+        resource_path_override::save(
+            &mut *conn,
+            resource_id,
+            &path_override.hostname,
+            &path_override.path,
+        )
+        .await?;
+    }
+
+    sync::modify_deleted_at(&mut *conn, RESOURCE_KIND, resource_id, remote.deleted_at).await?;
+
+    // 最后写入修改时间,保持与其他设备一致,避免互相覆盖
+    sync::modify_updated_at(&mut *conn, RESOURCE_KIND, resource_id, remote.updated_at).await?;
+
+    Ok(())
+}
+
+async fn record_conflict<T: Serialize>(
+    conn: &mut SqliteConnection,
+    kind: &str,
+    uuid: &str,
+    local: &T,
+    remote: &T,
+    remote_wins: bool,
+    remote_machine: &str,
+) -> Result<(), OneClickLaunchError> {
+    let local_value = serde_json::to_string(local)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+    let remote_value = serde_json::to_string(remote)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+    let winner = if remote_wins { "remote" } else { "local" };
+
+    sync::create_conflict(
+        &mut *conn,
+        kind,
+        uuid,
+        &local_value,
+        &remote_value,
+        winner,
+        remote_machine,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// 查询本机全部启动器的同步内容,返回(启动器id, 同步内容)
+async fn query_synced_launchers(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<(i64, SyncedLauncher)>, OneClickLaunchError> {
+    let folders = folder::query_all(&mut *conn).await?;
+    let launcher_tags = tag::query_launcher_tags(&mut *conn).await?;
+
+    let launchers = sync::query_launchers(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|l| {
+            let synced = SyncedLauncher {
+                uuid: l.uuid,
+                name: l.name,
+                sort: l.sort,
+                folder: l
+                    .folder_id
+                    .map(|folder_id| folder::name_path(&folders, folder_id))
+                    .unwrap_or_default(),
+                notes: l.notes,
+                tags: launcher_tags
+                    .iter()
+                    .filter(|lt| lt.launcher_id == l.id)
+                    .map(|lt| lt.name.clone())
+                    .collect(),
+                pre_launch_command: l.pre_launch_command,
+                post_launch_command: l.post_launch_command,
+                slug: l.slug,
+                updated_at: l.updated_at,
+                deleted_at: l.deleted_at,
+            };
+            (l.id, synced)
+        })
+        .collect();
+
+    Ok(launchers)
+}
+
+/// 查询本机全部资源的同步内容,返回(资源id, 同步内容)
+async fn query_synced_resources(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<(i64, SyncedResource)>, OneClickLaunchError> {
+    let launcher_uuids = sync::query_launchers(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|l| (l.id, l.uuid))
+        .collect::<HashMap<i64, String>>();
+
+    let path_overrides = resource_path_override::query_all(&mut *conn).await?;
+
+    let resources = sync::query_resources(&mut *conn)
+        .await?
+        .into_iter()
+        .filter_map(|r| {
+            let launcher_uuid = launcher_uuids.get(&r.launcher_id)?.clone();
+            let synced = SyncedResource {
+                uuid: r.uuid,
+                launcher_uuid,
+                name: r.name,
+                path: r.path,
+                path_overrides: path_overrides
+                    .iter()
+                    .filter(|o| o.resource_id == r.id)
+                    .map(|o| SyncedPathOverride {
+                        hostname: o.hostname.clone(),
+                        path: o.path.clone(),
+                    })
+                    .collect(),
+                updated_at: r.updated_at,
+                deleted_at: r.deleted_at,
+            };
+            Some((r.id, synced))
+        })
+        .collect();
+
+    Ok(resources)
+}
+
+/// 读取同步文件夹设置,为空表示未开启同步
+async fn read_sync_dir(pool: &SqlitePool) -> Result<Option<PathBuf>, OneClickLaunchError> {
+    let sync_dir = setting_registry::read_string(pool, SYNC_FOLDER_KEY).await?;
+    let sync_dir = sync_dir.trim();
+    Ok((!sync_dir.is_empty()).then(|| PathBuf::from(sync_dir)))
+}
+
+/// 读取本机的设备id,不存在时生成
+async fn read_machine_id(pool: &SqlitePool) -> Result<String, OneClickLaunchError> {
+    if let Some(setting) = settings::read(pool, SYNC_MACHINE_ID_KEY).await? {
+        return Ok(setting.value);
+    }
+
+    let machine_id = Uuid::new_v4().to_string();
+    settings::save(
+        pool,
+        &Settings {
+            key: SYNC_MACHINE_ID_KEY.to_string(),
+            value: machine_id.clone(),
+        },
+    )
+    .await?;
+
+    Ok(machine_id)
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
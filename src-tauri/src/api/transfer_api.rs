@@ -0,0 +1,401 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+
+use crate::{
+    DatabaseManager,
+    api::launcher_api,
+    constants::AUTO_START_LAUNCHER_IDS_KEY,
+    db::{
+        folder, launcher,
+        launcher_resource::{self, CreateResourceParam},
+        settings::{self, Settings},
+        tag,
+    },
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{
+            LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, ResourceCreated,
+            ResourceCreatedPayload,
+        },
+    },
+    setting_registry,
+};
+
+/// 导出文件格式版本,格式发生不兼容变更时递增
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// 导出文件
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LauncherDocument {
+    /// 导出文件格式版本
+    pub version: u32,
+    pub launchers: Vec<ExportedLauncher>,
+}
+
+/// 导出的启动器
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExportedLauncher {
+    pub name: String,
+    #[serde(default)]
+    pub sort: i32,
+    /// 从根目录开始的文件夹名称路径,为空表示位于根目录
+    #[serde(default)]
+    pub folder: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+    /// 启动前执行的命令
+    #[serde(default)]
+    pub pre_launch_command: String,
+    /// 启动后执行的命令
+    #[serde(default)]
+    pub post_launch_command: String,
+    /// 别名,导入时与现有启动器的别名重复则不设置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub settings: ExportedLauncherSettings,
+    #[serde(default)]
+    pub resources: Vec<ExportedResource>,
+}
+
+/// 导出的启动器级别设置
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ExportedLauncherSettings {
+    /// 是否随应用程序开机自启一同启动
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+/// 导出的资源
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExportedResource {
+    pub name: String,
+    pub path: String,
+}
+
+/// 导入时与现有启动器重名的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflictStrategy {
+    /// 跳过重名的启动器
+    Skip,
+    /// 为导入的启动器重新命名
+    Rename,
+    /// 删除现有的同名启动器(移入回收站)后导入
+    Replace,
+}
+
+impl std::str::FromStr for ImportConflictStrategy {
+    type Err = OneClickLaunchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "rename" => Ok(Self::Rename),
+            "replace" => Ok(Self::Replace),
+            _ => Err(OneClickLaunchError::ExecutionError(format!(
+                "不支持的冲突处理方式: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 导入结果
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ImportReportVo {
+    /// 新建的启动器id
+    pub imported: Vec<i64>,
+    /// 因重名被跳过的启动器名称
+    pub skipped: Vec<String>,
+    /// 因重名被替换(移入回收站)的启动器id
+    pub replaced: Vec<i64>,
+    /// 新建的资源id,用于发送资源事件
+    #[serde(skip)]
+    pub resource_ids: Vec<i64>,
+}
+
+/// 导出文件格式,按文件扩展名判断,默认为JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentFormat {
+    Json,
+    Toml,
+}
+
+impl DocumentFormat {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// 导出启动器到文件,`launcher_ids`为空时导出全部启动器
+#[tauri::command]
+pub async fn export_launchers(
+    db: State<'_, DatabaseManager>,
+    launcher_ids: Option<Vec<i64>>,
+    path: String,
+) -> Result<usize, OneClickLaunchError> {
+    export_to_file(&db.pool(), launcher_ids.as_deref(), Path::new(&path)).await
+}
+
+/// 从文件导入启动器
+#[tauri::command]
+pub async fn import_launchers(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    path: String,
+    conflict: ImportConflictStrategy,
+) -> Result<ImportReportVo, OneClickLaunchError> {
+    let pool = db.pool();
+    let report = import_from_file(&pool, Path::new(&path), conflict).await?;
+    let resources =
+        launcher_api::query_resource_vos(&mut *pool.acquire().await?, &report.resource_ids).await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: report
+                .imported
+                .iter()
+                .chain(report.replaced.iter())
+                .copied()
+                .collect(),
+        },
+    );
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(report)
+}
+
+/// 导出启动器到文件,返回导出的启动器数量
+pub async fn export_to_file(
+    pool: &SqlitePool,
+    launcher_ids: Option<&[i64]>,
+    path: &Path,
+) -> Result<usize, OneClickLaunchError> {
+    let document = build_document(pool, launcher_ids).await?;
+
+    let content = match DocumentFormat::from_path(path) {
+        DocumentFormat::Json => serde_json::to_string_pretty(&document)
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?,
+        DocumentFormat::Toml => toml::to_string_pretty(&document)
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?,
+    };
+
+    fs::write(path, content)?;
+
+    Ok(document.launchers.len())
+}
+
+/// 从文件导入启动器
+pub async fn import_from_file(
+    pool: &SqlitePool,
+    path: &Path,
+    conflict: ImportConflictStrategy,
+) -> Result<ImportReportVo, OneClickLaunchError> {
+    let content = fs::read_to_string(path)?;
+
+    let document: LauncherDocument = match DocumentFormat::from_path(path) {
+        DocumentFormat::Json => serde_json::from_str(&content)
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?,
+        DocumentFormat::Toml => toml::from_str(&content)
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?,
+    };
+
+    if document.version > EXPORT_FORMAT_VERSION {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "导入文件版本({})高于当前支持的版本({})",
+            document.version, EXPORT_FORMAT_VERSION
+        )));
+    }
+
+    import_document(pool, document, conflict).await
+}
+
+/// 构建导出文件内容
+pub async fn build_document(
+    pool: &SqlitePool,
+    launcher_ids: Option<&[i64]>,
+) -> Result<LauncherDocument, OneClickLaunchError> {
+    let mut launchers = launcher::query(pool).await?;
+    if let Some(launcher_ids) = launcher_ids {
+        launchers.retain(|l| launcher_ids.contains(&l.id));
+    }
+
+    let folders = folder::query_all(pool).await?;
+    let launcher_tags = tag::query_launcher_tags(pool).await?;
+    let resources = launcher_resource::query_all(pool).await?;
+    let auto_start_ids = read_auto_start_launcher_ids(pool).await?;
+
+    let launchers = launchers
+        .into_iter()
+        .map(|l| ExportedLauncher {
+            folder: l
+                .folder_id
+                .map(|folder_id| folder::name_path(&folders, folder_id))
+                .unwrap_or_default(),
+            tags: launcher_tags
+                .iter()
+                .filter(|lt| lt.launcher_id == l.id)
+                .map(|lt| lt.name.clone())
+                .collect(),
+            settings: ExportedLauncherSettings {
+                auto_start: auto_start_ids.contains(&l.id),
+            },
+            resources: resources
+                .iter()
+                // 资源按id倒序查询,导出时恢复为添加顺序
+                .rev()
+                .filter(|r| r.launcher_id == l.id)
+                .map(|r| ExportedResource {
+                    name: r.name.clone(),
+                    path: r.path.clone(),
+                })
+                .collect(),
+            name: l.name,
+            sort: l.sort,
+            notes: l.notes,
+            pre_launch_command: l.pre_launch_command,
+            post_launch_command: l.post_launch_command,
+            slug: l.slug,
+        })
+        .collect();
+
+    Ok(LauncherDocument {
+        version: EXPORT_FORMAT_VERSION,
+        launchers,
+    })
+}
+
+/// 把导出文件内容写入数据库
+pub async fn import_document(
+    pool: &SqlitePool,
+    document: LauncherDocument,
+    conflict: ImportConflictStrategy,
+) -> Result<ImportReportVo, OneClickLaunchError> {
+    let mut report = ImportReportVo::default();
+
+    let mut existing = launcher::query(pool).await?;
+    let mut auto_start_ids = read_auto_start_launcher_ids(pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    for exported in document.launchers {
+        let conflicting = existing
+            .iter()
+            .filter(|l| l.name == exported.name)
+            .map(|l| l.id)
+            .collect::<Vec<i64>>();
+
+        let mut name = exported.name.clone();
+
+        if !conflicting.is_empty() {
+            match conflict {
+                ImportConflictStrategy::Skip => {
+                    report.skipped.push(exported.name);
+                    continue;
+                }
+                ImportConflictStrategy::Rename => {
+                    name = unique_name(&existing, &exported.name);
+                }
+                ImportConflictStrategy::Replace => {
+                    for launcher_id in conflicting.iter().copied() {
+                        launcher::delete_by_id(&mut tx, launcher_id).await?;
+                        auto_start_ids.retain(|id| *id != launcher_id);
+                    }
+                    existing.retain(|l| !conflicting.contains(&l.id));
+                    report.replaced.extend(conflicting);
+                }
+            }
+        }
+
+        let launcher_id = launcher::create(&mut tx, &name, Some(exported.sort)).await?;
+
+        // 按名称路径查找文件夹,不存在时创建
+        let mut folder_id = None;
+        for folder_name in exported.folder.iter() {
+            folder_id = match folder::find_child(&mut tx, folder_id, folder_name).await? {
+                Some(f) => Some(f.id),
+                None => Some(folder::create(&mut tx, folder_id, folder_name).await?),
+            };
+        }
+        launcher::modify_launcher_folder(&mut tx, launcher_id, folder_id).await?;
+
+        launcher::modify_launcher_notes(&mut tx, launcher_id, &exported.notes).await?;
+
+        launcher::modify_launch_commands(
+            &mut tx,
+            launcher_id,
+            &exported.pre_launch_command,
+            &exported.post_launch_command,
+        )
+        .await?;
+
+        if let Some(slug) = exported.slug.as_deref().filter(|slug| {
+            !slug.is_empty() && existing.iter().all(|l| l.slug.as_deref() != Some(*slug))
+        }) {
+            launcher::modify_launcher_slug(&mut tx, launcher_id, Some(slug)).await?;
+        }
+
+        for tag_name in exported.tags.iter().filter(|t| !t.trim().is_empty()) {
+            let tag_id = tag::get_or_create(&mut tx, tag_name.trim()).await?;
+            tag::add_launcher_tag(&mut tx, launcher_id, tag_id).await?;
+        }
+
+        let resources = exported
+            .resources
+            .into_iter()
+            .map(|r| CreateResourceParam {
+                name: r.name,
+                path: r.path,
+            })
+            .collect::<Vec<CreateResourceParam>>();
+        report
+            .resource_ids
+            .extend(launcher_resource::create_resources(&mut tx, launcher_id, &resources).await?);
+
+        if exported.settings.auto_start {
+            auto_start_ids.push(launcher_id);
+        }
+
+        existing.push(launcher::find_by_id(&mut tx, launcher_id).await?);
+        report.imported.push(launcher_id);
+    }
+
+    let value = serde_json::to_string(&auto_start_ids)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+    settings::save(
+        &mut tx,
+        &Settings {
+            key: AUTO_START_LAUNCHER_IDS_KEY.to_string(),
+            value,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(report)
+}
+
+/// 生成与现有启动器不重名的名称
+fn unique_name(existing: &[launcher::Launcher], name: &str) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| existing.iter().all(|l| &l.name != candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+async fn read_auto_start_launcher_ids(pool: &SqlitePool) -> Result<Vec<i64>, OneClickLaunchError> {
+    setting_registry::read_id_list(pool, AUTO_START_LAUNCHER_IDS_KEY).await
+}
@@ -0,0 +1,223 @@
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+use tracing::debug;
+
+use crate::{
+    DatabaseManager,
+    api::{
+        launcher_api,
+        undo_api::{self, UndoOperation},
+    },
+    constants::TRASH_RETENTION_DAYS_KEY,
+    db::{launcher, launcher_resource, tag},
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{
+            LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, ResourceCreated,
+            ResourceCreatedPayload,
+        },
+    },
+    setting_registry,
+};
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TrashVo {
+    /// 被删除的启动器
+    pub launchers: Vec<TrashLauncherVo>,
+    /// 被单独删除的资源,不包含已删除启动器中的资源
+    pub resources: Vec<TrashResourceVo>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TrashLauncherVo {
+    pub id: i64,
+    pub name: String,
+    pub deleted_at: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TrashResourceVo {
+    pub id: i64,
+    pub launcher_id: i64,
+    pub launcher_name: String,
+    pub name: String,
+    pub path: String,
+    pub deleted_at: i64,
+}
+
+/// 查询回收站
+#[tauri::command]
+pub async fn query_trash(db: State<'_, DatabaseManager>) -> Result<TrashVo, OneClickLaunchError> {
+    let launchers = launcher::query_deleted(&db.pool())
+        .await?
+        .into_iter()
+        .map(|l| TrashLauncherVo {
+            id: l.id,
+            name: l.name,
+            deleted_at: l.deleted_at,
+        })
+        .collect();
+
+    let resources = launcher_resource::query_deleted(&db.pool())
+        .await?
+        .into_iter()
+        .map(|r| TrashResourceVo {
+            id: r.id,
+            launcher_id: r.launcher_id,
+            launcher_name: r.launcher_name,
+            name: r.name,
+            path: r.path,
+            deleted_at: r.deleted_at,
+        })
+        .collect();
+
+    Ok(TrashVo {
+        launchers,
+        resources,
+    })
+}
+
+/// 从回收站恢复启动器
+#[tauri::command]
+pub async fn restore_launcher(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    let deleted = launcher::query_deleted(&mut tx)
+        .await?
+        .into_iter()
+        .find(|l| l.id == launcher_id)
+        .ok_or_else(|| {
+            OneClickLaunchError::ExecutionError(format!("启动器不在回收站中: {}", launcher_id))
+        })?;
+
+    launcher::restore_by_id(&mut tx, launcher_id).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("恢复启动器: {}", deleted.name),
+        UndoOperation::DeleteLauncher { launcher_id },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        &app,
+        LauncherBasicInfoUpdatedPayload {
+            launcher_ids: vec![launcher_id],
+        },
+    );
+
+    Ok(())
+}
+
+/// 从回收站恢复资源,所属启动器也在回收站中时需要先恢复启动器
+#[tauri::command]
+pub async fn restore_resource(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+    resource_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = db.pool().begin().await?;
+
+    // 回收站中的资源不包含所属启动器已被删除的资源
+    let deleted = launcher_resource::query_deleted(&mut tx)
+        .await?
+        .into_iter()
+        .find(|r| r.id == resource_id)
+        .ok_or_else(|| {
+            OneClickLaunchError::ExecutionError(format!(
+                "资源不在回收站中,或所属启动器已被删除,请先恢复启动器: {}",
+                resource_id
+            ))
+        })?;
+
+    launcher_resource::restore_by_id(&mut tx, resource_id).await?;
+
+    undo_api::record(
+        &mut tx,
+        &format!("恢复资源: {}", deleted.name),
+        UndoOperation::DeleteResources {
+            resource_ids: vec![resource_id],
+        },
+    )
+    .await?;
+
+    let resources = launcher_api::query_resource_vos(&mut tx, &[resource_id]).await?;
+
+    tx.commit().await?;
+
+    let _ =
+        EventDispatcher::<ResourceCreated>::send_event(&app, ResourceCreatedPayload { resources });
+
+    Ok(())
+}
+
+/// 彻底删除回收站中的启动器,包括启动器中的资源
+#[tauri::command]
+pub async fn purge_launcher(
+    db: State<'_, DatabaseManager>,
+    launcher_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    purge_launchers(&db.pool(), &[launcher_id]).await?;
+    Ok(())
+}
+
+/// 彻底删除回收站中的资源
+#[tauri::command]
+pub async fn purge_resource(
+    db: State<'_, DatabaseManager>,
+    resource_id: i64,
+) -> Result<(), OneClickLaunchError> {
+    launcher_resource::purge_by_id(&db.pool(), resource_id).await?;
+    Ok(())
+}
+
+async fn purge_launchers(
+    pool: &SqlitePool,
+    launcher_ids: &[i64],
+) -> Result<(), OneClickLaunchError> {
+    let mut tx = pool.begin().await?;
+
+    for launcher_id in launcher_ids.iter().copied() {
+        // 只有回收站中的启动器才允许彻底删除
+        if launcher::purge_by_id(&mut tx, launcher_id).await? {
+            launcher_resource::delete_by_launcher(&mut tx, launcher_id).await?;
+
+            tag::delete_by_launcher(&mut tx, launcher_id).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// 彻底删除在回收站中超过保留天数的启动器与资源,保留天数为0时不自动清理
+pub async fn purge_expired_trash(pool: &SqlitePool) -> Result<(), OneClickLaunchError> {
+    let retention_days = setting_registry::read_integer(pool, TRASH_RETENTION_DAYS_KEY).await?;
+
+    if retention_days <= 0 {
+        debug!("purge_expired_trash 未开启回收站自动清理");
+        return Ok(());
+    }
+
+    let retention_secs = retention_days * 24 * 60 * 60;
+
+    let expired_launcher_ids = launcher::query_expired_ids(pool, retention_secs).await?;
+    purge_launchers(pool, &expired_launcher_ids).await?;
+
+    let purged_resources = launcher_resource::purge_expired(pool, retention_secs).await?;
+
+    debug!(
+        "purge_expired_trash 已清理启动器: {:?}, 资源数量: {}",
+        expired_launcher_ids, purged_resources
+    );
+
+    Ok(())
+}
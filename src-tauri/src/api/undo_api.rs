@@ -0,0 +1,430 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use tauri::{AppHandle, State};
+
+use crate::{
+    DatabaseManager,
+    api::launcher_api::{self, LauncherResourceVo, LauncherSort},
+    constants::UNDO_JOURNAL_LIMIT_KEY,
+    db::{
+        launcher, launcher_resource, resource_path_override, tag,
+        undo_journal::{self, REDO_STACK, UNDO_STACK},
+    },
+    error::OneClickLaunchError,
+    events::{
+        EventDispatcher,
+        types::{
+            LauncherBasicInfoUpdated, LauncherBasicInfoUpdatedPayload, ResourceCreated,
+            ResourceCreatedPayload, ResourceDeleted, ResourceDeletedPayload, ResourceUpdated,
+            ResourceUpdatedPayload,
+        },
+    },
+    setting_registry,
+};
+
+/// 可撤销的操作,撤销栈与重做栈中保存的都是需要执行的逆操作
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UndoOperation {
+    /// 修改启动器名称
+    RenameLauncher { launcher_id: i64, name: String },
+    /// 调整启动器顺序
+    SortLaunchers { launchers: Vec<LauncherSort> },
+    /// 移动启动器到文件夹
+    MoveLauncher {
+        launcher_id: i64,
+        folder_id: Option<i64>,
+    },
+    /// 修改启动器备注
+    ModifyLauncherNotes { launcher_id: i64, notes: String },
+    /// 修改启动器启动前后执行的命令
+    ModifyLaunchCommands {
+        launcher_id: i64,
+        pre_launch_command: String,
+        post_launch_command: String,
+    },
+    /// 设置启动器标签
+    SetLauncherTags { launcher_id: i64, tags: Vec<String> },
+    /// 删除启动器(移入回收站)
+    DeleteLauncher { launcher_id: i64 },
+    /// 从回收站恢复启动器
+    RestoreLauncher { launcher_id: i64 },
+    /// 删除资源(移入回收站)
+    DeleteResources { resource_ids: Vec<i64> },
+    /// 从回收站恢复资源
+    RestoreResources { resource_ids: Vec<i64> },
+    /// 修改资源名称
+    RenameResource { resource_id: i64, name: String },
+    /// 修改资源路径
+    ModifyResourcePath { resource_id: i64, path: String },
+    /// 设置资源在指定主机上的路径,`path`为空表示删除
+    SetResourcePathOverride {
+        resource_id: i64,
+        hostname: String,
+        path: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoStateVo {
+    /// 下一个可撤销操作的描述
+    pub undo_label: Option<String>,
+    /// 下一个可重做操作的描述
+    pub redo_label: Option<String>,
+}
+
+/// 记录一个可撤销的操作,`inverse`为撤销时需要执行的逆操作
+///
+/// 记录新操作时会清空重做栈,并按设置的上限裁剪撤销栈
+pub async fn record(
+    conn: &mut SqliteConnection,
+    label: &str,
+    inverse: UndoOperation,
+) -> Result<(), OneClickLaunchError> {
+    let operation = serde_json::to_string(&inverse)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+
+    undo_journal::push(&mut *conn, UNDO_STACK, label, &operation).await?;
+
+    undo_journal::clear(&mut *conn, REDO_STACK).await?;
+
+    let limit = read_journal_limit(conn).await?;
+    undo_journal::trim(&mut *conn, UNDO_STACK, limit).await?;
+
+    Ok(())
+}
+
+async fn read_journal_limit(conn: &mut SqliteConnection) -> Result<i64, OneClickLaunchError> {
+    setting_registry::read_integer(&mut *conn, UNDO_JOURNAL_LIMIT_KEY).await
+}
+
+/// 撤销上一个操作,返回被撤销操作的描述
+#[tauri::command]
+pub async fn undo(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+) -> Result<Option<String>, OneClickLaunchError> {
+    replay(&app, &db, UNDO_STACK, REDO_STACK).await
+}
+
+/// 重做上一个被撤销的操作,返回被重做操作的描述
+#[tauri::command]
+pub async fn redo(
+    app: AppHandle,
+    db: State<'_, DatabaseManager>,
+) -> Result<Option<String>, OneClickLaunchError> {
+    replay(&app, &db, REDO_STACK, UNDO_STACK).await
+}
+
+/// 查询撤销/重做状态
+#[tauri::command]
+pub async fn query_undo_state(
+    db: State<'_, DatabaseManager>,
+) -> Result<UndoStateVo, OneClickLaunchError> {
+    let undo_label = undo_journal::find_last(&db.pool(), UNDO_STACK)
+        .await?
+        .map(|j| j.label);
+    let redo_label = undo_journal::find_last(&db.pool(), REDO_STACK)
+        .await?
+        .map(|j| j.label);
+    Ok(UndoStateVo {
+        undo_label,
+        redo_label,
+    })
+}
+
+/// 从`from`栈弹出一个操作并执行,再把执行得到的逆操作压入`to`栈
+async fn replay(
+    app: &AppHandle,
+    db: &DatabaseManager,
+    from: &str,
+    to: &str,
+) -> Result<Option<String>, OneClickLaunchError> {
+    let Some(journal) = undo_journal::find_last(&db.pool(), from).await? else {
+        return Ok(None);
+    };
+
+    let mut tx = db.pool().begin().await?;
+
+    let result = match serde_json::from_str::<UndoOperation>(&journal.operation) {
+        Ok(operation) => apply_and_collect(&mut tx, &operation).await,
+        Err(e) => Err(OneClickLaunchError::ExecutionError(e.to_string())),
+    };
+
+    let (inverse, launcher_ids, changes) = match result {
+        Ok(applied) => applied,
+        Err(e) => {
+            drop(tx);
+            // 无法执行的记录(如数据已被彻底删除)直接丢弃,避免阻塞后续的撤销
+            undo_journal::delete_by_id(&db.pool(), journal.id).await?;
+            return Err(e);
+        }
+    };
+
+    let operation = serde_json::to_string(&inverse)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+
+    undo_journal::delete_by_id(&mut tx, journal.id).await?;
+    undo_journal::push(&mut tx, to, &journal.label, &operation).await?;
+
+    tx.commit().await?;
+
+    let _ = EventDispatcher::<LauncherBasicInfoUpdated>::send_event(
+        app,
+        LauncherBasicInfoUpdatedPayload { launcher_ids },
+    );
+    changes.send_events(app);
+
+    Ok(Some(journal.label))
+}
+
+/// 操作引起的资源变更,用于发送资源事件
+#[derive(Default)]
+struct ResourceChanges {
+    created: Vec<LauncherResourceVo>,
+    updated: Vec<LauncherResourceVo>,
+    deleted: Vec<LauncherResourceVo>,
+}
+
+impl ResourceChanges {
+    fn send_events(self, app: &AppHandle) {
+        if !self.created.is_empty() {
+            let _ = EventDispatcher::<ResourceCreated>::send_event(
+                app,
+                ResourceCreatedPayload {
+                    resources: self.created,
+                },
+            );
+        }
+        if !self.updated.is_empty() {
+            let _ = EventDispatcher::<ResourceUpdated>::send_event(
+                app,
+                ResourceUpdatedPayload {
+                    resources: self.updated,
+                },
+            );
+        }
+        if !self.deleted.is_empty() {
+            let _ = EventDispatcher::<ResourceDeleted>::send_event(
+                app,
+                ResourceDeletedPayload {
+                    resources: self.deleted,
+                },
+            );
+        }
+    }
+}
+
+/// 执行操作并读取资源变更,删除的资源需要在执行前读取
+async fn apply_and_collect(
+    conn: &mut SqliteConnection,
+    operation: &UndoOperation,
+) -> Result<(UndoOperation, Vec<i64>, ResourceChanges), OneClickLaunchError> {
+    let mut changes = ResourceChanges::default();
+
+    if let UndoOperation::DeleteResources { resource_ids } = operation {
+        changes.deleted = launcher_api::query_resource_vos(&mut *conn, resource_ids).await?;
+    }
+
+    let (inverse, launcher_ids) = apply(&mut *conn, operation).await?;
+
+    match operation {
+        UndoOperation::RestoreResources { resource_ids } => {
+            changes.created = launcher_api::query_resource_vos(&mut *conn, resource_ids).await?;
+        }
+        UndoOperation::RenameResource { resource_id, .. }
+        | UndoOperation::ModifyResourcePath { resource_id, .. }
+        | UndoOperation::SetResourcePathOverride { resource_id, .. } => {
+            changes.updated = launcher_api::query_resource_vos(&mut *conn, &[*resource_id]).await?;
+        }
+        _ => {}
+    }
+
+    Ok((inverse, launcher_ids, changes))
+}
+
+/// 执行操作,返回该操作的逆操作以及受影响的启动器id
+async fn apply(
+    conn: &mut SqliteConnection,
+    operation: &UndoOperation,
+) -> Result<(UndoOperation, Vec<i64>), OneClickLaunchError> {
+    let applied = match operation {
+        UndoOperation::RenameLauncher { launcher_id, name } => {
+            let current = launcher::find_by_id(&mut *conn, *launcher_id).await?;
+            launcher::modify_launcher_name(&mut *conn, *launcher_id, name).await?;
+            (
+                UndoOperation::RenameLauncher {
+                    launcher_id: *launcher_id,
+                    name: current.name,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::ModifyLauncherNotes { launcher_id, notes } => {
+            let current = launcher::find_by_id(&mut *conn, *launcher_id).await?;
+            launcher::modify_launcher_notes(&mut *conn, *launcher_id, notes).await?;
+            (
+                UndoOperation::ModifyLauncherNotes {
+                    launcher_id: *launcher_id,
+                    notes: current.notes,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::ModifyLaunchCommands {
+            launcher_id,
+            pre_launch_command,
+            post_launch_command,
+        } => {
+            let current = launcher::find_by_id(&mut *conn, *launcher_id).await?;
+            launcher::modify_launch_commands(
+                &mut *conn,
+                *launcher_id,
+                pre_launch_command,
+                post_launch_command,
+            )
+            .await?;
+            (
+                UndoOperation::ModifyLaunchCommands {
+                    launcher_id: *launcher_id,
+                    pre_launch_command: current.pre_launch_command,
+                    post_launch_command: current.post_launch_command,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::SortLaunchers { launchers } => {
+            let mut current = Vec::with_capacity(launchers.len());
+            for ls in launchers.iter() {
+                let l = launcher::find_by_id(&mut *conn, ls.id).await?;
+                current.push(LauncherSort {
+                    id: l.id,
+                    sort: l.sort,
+                });
+                launcher::modify_launcher_sort(&mut *conn, ls.id, ls.sort).await?;
+            }
+            (
+                UndoOperation::SortLaunchers { launchers: current },
+                launchers.iter().map(|ls| ls.id).collect(),
+            )
+        }
+        UndoOperation::MoveLauncher {
+            launcher_id,
+            folder_id,
+        } => {
+            let current = launcher::find_by_id(&mut *conn, *launcher_id).await?;
+            launcher::modify_launcher_folder(&mut *conn, *launcher_id, *folder_id).await?;
+            (
+                UndoOperation::MoveLauncher {
+                    launcher_id: *launcher_id,
+                    folder_id: current.folder_id,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::SetLauncherTags { launcher_id, tags } => {
+            let current = tag::query_by_launcher_id(&mut *conn, *launcher_id)
+                .await?
+                .into_iter()
+                .map(|lt| lt.name)
+                .collect();
+            tag::delete_by_launcher(&mut *conn, *launcher_id).await?;
+            for name in tags.iter() {
+                let tag_id = tag::get_or_create(&mut *conn, name).await?;
+                tag::add_launcher_tag(&mut *conn, *launcher_id, tag_id).await?;
+            }
+            (
+                UndoOperation::SetLauncherTags {
+                    launcher_id: *launcher_id,
+                    tags: current,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::DeleteLauncher { launcher_id } => {
+            launcher::delete_by_id(&mut *conn, *launcher_id).await?;
+            (
+                UndoOperation::RestoreLauncher {
+                    launcher_id: *launcher_id,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::RestoreLauncher { launcher_id } => {
+            launcher::restore_by_id(&mut *conn, *launcher_id).await?;
+            (
+                UndoOperation::DeleteLauncher {
+                    launcher_id: *launcher_id,
+                },
+                vec![*launcher_id],
+            )
+        }
+        UndoOperation::DeleteResources { resource_ids } => {
+            for resource_id in resource_ids.iter() {
+                launcher_resource::delete_by_id(&mut *conn, *resource_id).await?;
+            }
+            (
+                UndoOperation::RestoreResources {
+                    resource_ids: resource_ids.clone(),
+                },
+                vec![],
+            )
+        }
+        UndoOperation::RestoreResources { resource_ids } => {
+            for resource_id in resource_ids.iter() {
+                launcher_resource::restore_by_id(&mut *conn, *resource_id).await?;
+            }
+            (
+                UndoOperation::DeleteResources {
+                    resource_ids: resource_ids.clone(),
+                },
+                vec![],
+            )
+        }
+        UndoOperation::RenameResource { resource_id, name } => {
+            let current = launcher_resource::find_by_id(&mut *conn, *resource_id).await?;
+            launcher_resource::modify_name(&mut *conn, *resource_id, name).await?;
+            (
+                UndoOperation::RenameResource {
+                    resource_id: *resource_id,
+                    name: current.name,
+                },
+                vec![],
+            )
+        }
+        UndoOperation::ModifyResourcePath { resource_id, path } => {
+            let current = launcher_resource::find_by_id(&mut *conn, *resource_id).await?;
+            launcher_resource::modify_path(&mut *conn, *resource_id, path).await?;
+            (
+                UndoOperation::ModifyResourcePath {
+                    resource_id: *resource_id,
+                    path: current.path,
+                },
+                vec![],
+            )
+        }
+        UndoOperation::SetResourcePathOverride {
+            resource_id,
+            hostname,
+            path,
+        } => {
+            let current = resource_path_override::find(&mut *conn, *resource_id, hostname).await?;
+            match path {
+                Some(path) => {
+                    resource_path_override::save(&mut *conn, *resource_id, hostname, path).await?
+                }
+                None => resource_path_override::delete(&mut *conn, *resource_id, hostname).await?,
+            }
+            (
+                UndoOperation::SetResourcePathOverride {
+                    resource_id: *resource_id,
+                    hostname: hostname.clone(),
+                    path: current.map(|o| o.path),
+                },
+                vec![],
+            )
+        }
+    };
+
+    Ok(applied)
+}
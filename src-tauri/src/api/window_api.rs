@@ -2,7 +2,7 @@ use std::{sync::Mutex, time::Instant};
 
 use tauri::{
     AppHandle, DragDropEvent, Manager, State, Theme,
-    menu::{MenuBuilder, MenuItem},
+    menu::{MenuBuilder, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
 };
 use tracing::{debug, info};
@@ -10,15 +10,19 @@ use tracing::{debug, info};
 use crate::{
     DatabaseManager, WindowContext,
     constants::{self, WINDOW_MIN_HEIGHT, WINDOW_MIN_WIDTH},
-    db::launcher,
+    db::{
+        folder::{self, Folder},
+        launcher::{self, Launcher},
+    },
     error::OneClickLaunchError,
     events::{
         EventDispatcher,
         types::{DragDropResource, DragDropResourcePaylod},
     },
+    setting_registry,
 };
 
-use super::{launcher_api, setting_api};
+use super::launcher_api;
 
 pub fn hide_window(app: &AppHandle) -> Result<(), OneClickLaunchError> {
     let window = app
@@ -33,15 +37,19 @@ pub async fn refresh_tray(app: AppHandle) -> Result<(), OneClickLaunchError> {
     // 获取全局状态
     let window_context: State<'_, WindowContext> = app.state();
     let database_manager: State<'_, DatabaseManager> = app.state();
-    let launchers = launcher::query(&database_manager.pool).await?;
+    let launchers = launcher::query(&database_manager.pool()).await?;
+    let folders = folder::query_all(&database_manager.pool()).await?;
 
     let mut menu_builder = MenuBuilder::new(&app);
+    // 文件夹以子菜单的形式展示
+    for folder in folders.iter().filter(|f| f.parent_id.is_none()) {
+        if let Some(submenu) = build_folder_submenu(&app, folder, &folders, &launchers)? {
+            menu_builder = menu_builder.item(&submenu);
+        }
+    }
     // 创建动态菜单项
-    for launcher in &launchers {
-        let id = format!("launch_{}", launcher.id);
-        let title = format!("启动: {}", launcher.name);
-        menu_builder =
-            menu_builder.item(&MenuItem::with_id(&app, &id, &title, true, None::<&str>)?);
+    for launcher in launchers.iter().filter(|l| l.folder_id.is_none()) {
+        menu_builder = menu_builder.item(&build_launcher_menu_item(&app, launcher)?);
     }
 
     if !launchers.is_empty() {
@@ -57,6 +65,40 @@ pub async fn refresh_tray(app: AppHandle) -> Result<(), OneClickLaunchError> {
     Ok(())
 }
 
+fn build_launcher_menu_item(
+    app: &AppHandle,
+    launcher: &Launcher,
+) -> Result<MenuItem<tauri::Wry>, OneClickLaunchError> {
+    let id = format!("launch_{}", launcher.id);
+    let title = format!("启动: {}", launcher.name);
+    Ok(MenuItem::with_id(app, &id, &title, true, None::<&str>)?)
+}
+
+/// 递归构建文件夹子菜单,不包含任何启动器的文件夹不会展示
+fn build_folder_submenu(
+    app: &AppHandle,
+    folder: &Folder,
+    folders: &[Folder],
+    launchers: &[Launcher],
+) -> Result<Option<Submenu<tauri::Wry>>, OneClickLaunchError> {
+    let submenu = Submenu::new(app, &folder.name, true)?;
+    let mut is_empty = true;
+
+    for child in folders.iter().filter(|f| f.parent_id == Some(folder.id)) {
+        if let Some(child_submenu) = build_folder_submenu(app, child, folders, launchers)? {
+            submenu.append(&child_submenu)?;
+            is_empty = false;
+        }
+    }
+
+    for launcher in launchers.iter().filter(|l| l.folder_id == Some(folder.id)) {
+        submenu.append(&build_launcher_menu_item(app, launcher)?)?;
+        is_empty = false;
+    }
+
+    Ok((!is_empty).then_some(submenu))
+}
+
 pub fn change_windows_theme(app: &AppHandle, theme: &str) -> Result<(), OneClickLaunchError> {
     if let Some(window) = app.get_webview_window(constants::MAIN_WINDOW_LABEL) {
         window.set_theme(match theme {
@@ -77,13 +119,12 @@ pub fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
             let app_handle = window.app_handle().clone();
             tauri::async_runtime::spawn(async move {
                 let db = app_handle.state::<DatabaseManager>();
-                let setting = setting_api::read_setting(db, constants::CLOSE_MAIN_PANEL_KEY).await;
+                let setting =
+                    setting_registry::read_string(&db.pool(), constants::CLOSE_MAIN_PANEL_KEY)
+                        .await;
 
-                match setting {
-                    Ok(Some(setting)) if constants::CLOSE_MAIN_PANEL_EXIT == setting.value => {
-                        app_handle.exit(0);
-                    }
-                    _ => {}
+                if setting.is_ok_and(|value| value == constants::CLOSE_MAIN_PANEL_EXIT) {
+                    app_handle.exit(0);
                 }
             });
         }
@@ -0,0 +1,380 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    DatabaseManager,
+    api::{
+        hook_api,
+        launcher_api::{self, LaunchReport, LauncherVo, ResourceParam},
+        transfer_api::{self, ImportConflictStrategy},
+    },
+    error::OneClickLaunchError,
+    events::{
+        Event,
+        types::{LauncherLaunched, LauncherLaunchedPayload},
+    },
+};
+
+/// 命令执行成功
+pub const EXIT_SUCCESS: i32 = 0;
+/// 命令执行失败
+pub const EXIT_FAILURE: i32 = 1;
+// 参数无效时clap使用的退出码为2
+/// 找不到指定的启动器
+pub const EXIT_LAUNCHER_NOT_FOUND: i32 = 3;
+/// 匹配到多个启动器
+pub const EXIT_AMBIGUOUS_LAUNCHER: i32 = 4;
+/// 启动被取消,或有资源、启动前后命令执行失败
+pub const EXIT_LAUNCH_FAILED: i32 = 5;
+
+/// 命令行参数,在启动Tauri之前解析
+#[derive(Parser, Debug)]
+#[command(name = "one-click-launch", version, about = "一键启动")]
+pub struct Cli {
+    /// 使用指定的配置(配置id或名称)
+    #[arg(long, global = true, value_name = "配置")]
+    pub profile: Option<String>,
+
+    /// 使用指定的数据目录
+    #[arg(long, global = true, value_name = "目录")]
+    pub data_dir: Option<PathBuf>,
+
+    /// 操作系统开机自启时传入,启动设置的自启启动器
+    #[arg(long, hide = true)]
+    pub auto: bool,
+
+    /// 以JSON格式输出结果,错误以`{"error": 错误信息}`的格式输出到标准错误
+    ///
+    /// 与`launch`一起使用时在当前进程中启动并输出启动报告,不转发给运行中的应用程序
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// 列出全部启动器
+    List,
+    /// 启动指定的启动器,应用程序未运行时会先启动应用程序
+    Launch {
+        /// 启动器id、别名、名称或名称前缀
+        launcher: String,
+    },
+    /// 新建启动器
+    Add {
+        /// 启动器名称
+        name: String,
+        /// 启动器中的资源路径
+        resources: Vec<String>,
+    },
+    /// 删除启动器,启动器会被移入回收站
+    Remove {
+        /// 启动器id、别名、名称或名称前缀
+        launcher: String,
+    },
+    /// 重命名启动器
+    Rename {
+        /// 启动器id、别名、名称或名称前缀
+        launcher: String,
+        /// 新名称
+        name: String,
+    },
+    /// 显示启动器的详细信息
+    Show {
+        /// 启动器id、别名、名称或名称前缀
+        launcher: String,
+    },
+    /// 导出全部启动器,文件扩展名为`.toml`时导出为TOML,否则为JSON
+    Export {
+        /// 导出文件路径
+        path: PathBuf,
+    },
+    /// 导入启动器
+    Import {
+        /// 导入文件路径
+        path: PathBuf,
+        /// 重名启动器的处理方式: skip|rename|replace
+        #[arg(long, default_value = "skip")]
+        conflict: ImportConflictStrategy,
+    },
+    /// 退出正在运行的应用程序
+    Quit,
+}
+
+impl Cli {
+    /// 解析其他实例转发过来的参数,参数无效时返回None
+    pub fn from_argv(argv: &[String]) -> Option<Self> {
+        Self::try_parse_from(argv).ok()
+    }
+
+    /// 需要启动的启动器,可以是启动器id、别名、名称或名称前缀
+    pub fn launcher_to_launch(&self) -> Option<&str> {
+        match &self.command {
+            Some(Command::Launch { launcher }) => Some(launcher),
+            _ => None,
+        }
+    }
+
+    /// 需要在当前进程中启动并输出启动报告的启动器
+    pub fn launcher_to_report(&self) -> Option<&str> {
+        self.launcher_to_launch().filter(|_| self.json)
+    }
+
+    /// 是否会打开应用程序窗口,其他情况下不能弹窗等待用户操作
+    pub fn is_interactive(&self) -> bool {
+        match &self.command {
+            None => true,
+            Some(Command::Launch { .. }) => !self.json,
+            Some(_) => false,
+        }
+    }
+
+    /// 是否要求退出应用程序
+    pub fn is_quit(&self) -> bool {
+        matches!(self.command, Some(Command::Quit))
+    }
+
+    /// 是否为修改启动器的命令,执行成功后需要通知运行中的应用程序刷新
+    pub fn modifies_launchers(&self) -> bool {
+        matches!(
+            self.command,
+            Some(
+                Command::Add { .. }
+                    | Command::Remove { .. }
+                    | Command::Rename { .. }
+                    | Command::Import { .. }
+            )
+        )
+    }
+}
+
+/// 导出结果
+#[derive(Serialize, Debug)]
+struct ExportReportVo {
+    path: PathBuf,
+    count: usize,
+}
+
+/// 执行无需打开窗口的命令,返回进程退出码,没有需要执行的命令时返回None
+///
+/// `launch`和`quit`需要与运行中的应用程序交互,不在这里执行
+pub async fn run_headless_command(cli: &Cli, pool: &SqlitePool) -> Option<i32> {
+    let command = cli.command.as_ref()?;
+    if matches!(command, Command::Launch { .. } | Command::Quit) {
+        return None;
+    }
+
+    match execute(command, cli.json, pool).await {
+        Ok(()) => Some(EXIT_SUCCESS),
+        Err(e) => {
+            print_error(cli.json, &e);
+            Some(exit_code(&e))
+        }
+    }
+}
+
+/// 检查`launch`指定的启动器是否存在且唯一,不满足时输出错误并返回进程退出码
+///
+/// 启动请求会转发给运行中的应用程序,在转发前检查才能让调用方得到错误
+pub async fn check_launch_target(cli: &Cli, pool: &SqlitePool) -> Option<i32> {
+    let launcher = cli.launcher_to_launch()?;
+    match launcher_api::resolve_launcher(pool, launcher).await {
+        Ok(_) => None,
+        Err(e) => {
+            print_error(cli.json, &e);
+            Some(exit_code(&e))
+        }
+    }
+}
+
+async fn execute(
+    command: &Command,
+    json: bool,
+    pool: &SqlitePool,
+) -> Result<(), OneClickLaunchError> {
+    match command {
+        Command::List => {
+            let launchers = launcher_api::query_launcher_vos(pool, None, None).await?;
+            if json {
+                return print_json(&launchers);
+            }
+            for launcher in launchers {
+                println!(
+                    "{}\t{}\t{}个资源",
+                    launcher.id,
+                    launcher.name,
+                    launcher.resources.len()
+                );
+            }
+        }
+        Command::Add { name, resources } => {
+            // 资源添加失败时不保留新建的启动器
+            let mut tx = pool.begin().await?;
+            let launcher_id = launcher_api::create_launcher(&mut tx, name).await?;
+            if !resources.is_empty() {
+                launcher_api::create_launcher_resources(
+                    &mut tx,
+                    launcher_id,
+                    resources
+                        .iter()
+                        .map(|path| ResourceParam {
+                            name: None,
+                            path: path.clone(),
+                        })
+                        .collect(),
+                )
+                .await?;
+            }
+            tx.commit().await?;
+            if json {
+                return print_json(&find_launcher_vo(pool, launcher_id).await?);
+            }
+            println!("已新建启动器: {} ({})", name, launcher_id);
+        }
+        Command::Remove { launcher } => {
+            let launcher_id = launcher_api::resolve_launcher(pool, launcher).await?;
+            let removed = find_launcher_vo(pool, launcher_id).await?;
+            launcher_api::remove_launcher(pool, launcher_id).await?;
+            if json {
+                return print_json(&removed);
+            }
+            println!("已将启动器 {} 移入回收站", launcher_id);
+        }
+        Command::Rename { launcher, name } => {
+            let launcher_id = launcher_api::resolve_launcher(pool, launcher).await?;
+            launcher_api::rename_launcher(pool, launcher_id, name).await?;
+            if json {
+                return print_json(&find_launcher_vo(pool, launcher_id).await?);
+            }
+            println!("已将启动器 {} 重命名为: {}", launcher_id, name);
+        }
+        Command::Show { launcher } => {
+            let launcher_id = launcher_api::resolve_launcher(pool, launcher).await?;
+            let launcher = find_launcher_vo(pool, launcher_id).await?;
+            if json {
+                return print_json(&launcher);
+            }
+            print_launcher(&launcher);
+        }
+        Command::Export { path } => {
+            let count = transfer_api::export_to_file(pool, None, path).await?;
+            if json {
+                return print_json(&ExportReportVo {
+                    path: path.clone(),
+                    count,
+                });
+            }
+            println!("已导出{}个启动器到: {}", count, path.to_string_lossy());
+        }
+        Command::Import { path, conflict } => {
+            let report = transfer_api::import_from_file(pool, path, *conflict).await?;
+            if json {
+                return print_json(&report);
+            }
+            println!(
+                "导入完成: 新建{}个, 跳过{}个, 替换{}个",
+                report.imported.len(),
+                report.skipped.len(),
+                report.replaced.len()
+            );
+        }
+        Command::Launch { .. } | Command::Quit => {}
+    }
+
+    Ok(())
+}
+
+/// 在当前进程中启动启动器并以JSON格式输出启动报告,返回进程退出码
+///
+/// 当前进程没有注册事件监听器,启动完成后直接执行启动事件上的钩子
+pub async fn launch_and_report(app: &AppHandle, launcher: &str) -> i32 {
+    let result = async {
+        let pool = app.state::<DatabaseManager>().pool();
+        let launcher_id = launcher_api::resolve_launcher(&pool, launcher).await?;
+        let report = launcher_api::launch(app.clone(), launcher_id).await?;
+
+        if !report.aborted {
+            let payload = LauncherLaunchedPayload {
+                launcher_ids: vec![launcher_id],
+            };
+            // 钩子失败不影响启动结果
+            let _ = hook_api::run_hooks(app.clone(), LauncherLaunched::name(), payload).await;
+        }
+
+        Ok::<LaunchReport, OneClickLaunchError>(report)
+    }
+    .await;
+
+    match result.and_then(|report| print_json(&report).map(|_| report)) {
+        Ok(report) if report.succeeded() => EXIT_SUCCESS,
+        Ok(_) => EXIT_LAUNCH_FAILED,
+        Err(e) => {
+            print_error(true, &e);
+            exit_code(&e)
+        }
+    }
+}
+
+fn exit_code(e: &OneClickLaunchError) -> i32 {
+    match e {
+        OneClickLaunchError::LauncherNotFound(_) => EXIT_LAUNCHER_NOT_FOUND,
+        OneClickLaunchError::AmbiguousLauncher(_) => EXIT_AMBIGUOUS_LAUNCHER,
+        _ => EXIT_FAILURE,
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), OneClickLaunchError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+pub fn print_error(json: bool, e: &OneClickLaunchError) {
+    if json {
+        eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
+    } else {
+        eprintln!("{}", e);
+    }
+}
+
+async fn find_launcher_vo(
+    pool: &SqlitePool,
+    launcher_id: i64,
+) -> Result<LauncherVo, OneClickLaunchError> {
+    launcher_api::query_launcher_vos(pool, None, None)
+        .await?
+        .into_iter()
+        .find(|launcher| launcher.id == launcher_id)
+        .ok_or_else(|| OneClickLaunchError::LauncherNotFound(launcher_id.to_string()))
+}
+
+fn print_launcher(launcher: &LauncherVo) {
+    println!("id: {}", launcher.id);
+    println!("名称: {}", launcher.name);
+    if let Some(slug) = &launcher.slug {
+        println!("别名: {}", slug);
+    }
+    if !launcher.tags.is_empty() {
+        println!("标签: {}", launcher.tags.join(", "));
+    }
+    if !launcher.notes.is_empty() {
+        println!("备注: {}", launcher.notes);
+    }
+    if !launcher.pre_launch_command.is_empty() {
+        println!("启动前命令: {}", launcher.pre_launch_command);
+    }
+    if !launcher.post_launch_command.is_empty() {
+        println!("启动后命令: {}", launcher.post_launch_command);
+    }
+    println!("资源:");
+    for resource in launcher.resources.iter() {
+        println!("  {}\t{}\t{}", resource.id, resource.name, resource.path);
+    }
+}
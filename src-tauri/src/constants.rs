@@ -2,15 +2,30 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     pub static ref AUTO_START_FLAG: String = "--auto".to_string();
-    pub static ref LAUNCH_SPECIFIED_LAUNCHER_KEY: String = "launch".to_string();
 }
 
+/// 指定数据目录的环境变量
+pub static DATA_DIR_ENV: &str = "ONE_CLICK_LAUNCH_DATA_DIR";
 pub static APPLICATION_NAME: &str = "一键启动";
 pub static MAIN_WINDOW_LABEL: &str = "main";
 pub static THEME_KEY: &str = "theme";
 pub static CLOSE_MAIN_PANEL_KEY: &str = "close_main_panel";
 pub static AUTO_START_LAUNCHER_IDS_KEY: &str = "auto_start_launcher_ids";
 pub static HIDE_AFTER_AUTO_START_KEY: &str = "hide_after_auto_start";
+/// 关闭主面板时最小化到托盘
+pub static CLOSE_MAIN_PANEL_MINIMIZE: &str = "m1";
+/// 关闭主面板时退出程序
 pub static CLOSE_MAIN_PANEL_EXIT: &str = "m2";
+pub static LAUNCH_THEN_EXIT_KEY: &str = "launch_then_exit";
+pub static EDIT_MODE_KEY: &str = "editMode";
+pub static TRASH_RETENTION_DAYS_KEY: &str = "trash_retention_days";
+pub static UNDO_JOURNAL_LIMIT_KEY: &str = "undo_journal_limit";
+pub static BACKUP_INTERVAL_HOURS_KEY: &str = "backup_interval_hours";
+pub static BACKUP_KEEP_COUNT_KEY: &str = "backup_keep_count";
+pub static SYNC_FOLDER_KEY: &str = "sync_folder";
+pub static SYNC_MACHINE_ID_KEY: &str = "sync_machine_id";
+pub static SYNC_INTERVAL_SECS_KEY: &str = "sync_interval_secs";
+/// 数据库结构版本,表结构发生变化时递增,版本升级前会自动备份数据库
+pub static SCHEMA_VERSION: i64 = 6;
 pub static WINDOW_MIN_WIDTH: u32 = 800;
 pub static WINDOW_MIN_HEIGHT: u32 = 600;
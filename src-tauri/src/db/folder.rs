@@ -0,0 +1,176 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite};
+
+/// 使用FromRow宏把数据库中读取出来的数据转换成Folder结构
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct Folder {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub sort: i32,
+}
+
+pub async fn initialize<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS folder(
+                id          INTEGER PRIMARY KEY NOT NULL,
+                parent_id   INTEGER,
+                name        VARCHAR             NOT NULL,
+                sort        INTEGER             NOT NULL DEFAULT 1);
+                CREATE INDEX IF NOT EXISTS idx_folder_parentid ON folder(parent_id);"#,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 新增文件夹
+pub async fn create<'a, E>(executor: E, parent_id: Option<i64>, name: &str) -> Result<i64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query("INSERT INTO folder (parent_id,name) VALUES (?,?)")
+        .bind(parent_id)
+        .bind(name)
+        .execute(executor)
+        .await?
+        .last_insert_rowid();
+    Ok(id)
+}
+
+/// 修改文件夹名称
+pub async fn modify_name<'a, E>(executor: E, id: i64, name: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE folder SET name = ? WHERE id = ?")
+        .bind(name)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 修改文件夹的上级文件夹
+pub async fn modify_parent<'a, E>(executor: E, id: i64, parent_id: Option<i64>) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE folder SET parent_id = ? WHERE id = ?")
+        .bind(parent_id)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 将指定文件夹下的子文件夹全部移动到另一个文件夹
+pub async fn move_children<'a, E>(
+    executor: E,
+    parent_id: i64,
+    target_parent_id: Option<i64>,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE folder SET parent_id = ? WHERE parent_id = ?")
+        .bind(target_parent_id)
+        .bind(parent_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 删除文件夹
+pub async fn delete_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM folder WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 查询单个文件夹信息
+pub async fn find_by_id<'a, E>(executor: E, id: i64) -> Result<Folder>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let folder = sqlx::query_as("SELECT id,parent_id,name,sort FROM folder WHERE id = ?")
+        .bind(id)
+        .fetch_one(executor)
+        .await?;
+    Ok(folder)
+}
+
+/// 按名称查询指定文件夹下的子文件夹,`parent_id`为空时查询根目录
+pub async fn find_child<'a, E>(
+    executor: E,
+    parent_id: Option<i64>,
+    name: &str,
+) -> Result<Option<Folder>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let folder = sqlx::query_as(
+        "SELECT id,parent_id,name,sort FROM folder WHERE parent_id IS ? AND name = ? ORDER BY id ASC LIMIT 1",
+    )
+    .bind(parent_id)
+    .bind(name)
+    .fetch_optional(executor)
+    .await?;
+    Ok(folder)
+}
+
+/// 查询全部文件夹
+pub async fn query_all<'a, E>(executor: E) -> Result<Vec<Folder>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let folders =
+        sqlx::query_as("SELECT id,parent_id,name,sort FROM folder ORDER BY sort ASC, id ASC")
+            .fetch_all(executor)
+            .await?;
+    Ok(folders)
+}
+
+/// 计算从根目录到指定文件夹的名称路径
+pub fn name_path(folders: &[Folder], folder_id: i64) -> Vec<String> {
+    let mut path = vec![];
+    let mut current = folders.iter().find(|f| f.id == folder_id);
+    while let Some(folder) = current {
+        // 防御错误数据形成的环
+        if path.len() > folders.len() {
+            break;
+        }
+        path.push(folder.name.clone());
+        current = folder
+            .parent_id
+            .and_then(|parent_id| folders.iter().find(|f| f.id == parent_id));
+    }
+    path.reverse();
+    path
+}
+
+/// 计算指定文件夹及其全部子孙文件夹的id
+pub fn descendant_ids(folders: &[Folder], folder_id: i64) -> Vec<i64> {
+    let mut ids = vec![folder_id];
+    let mut index = 0;
+    while index < ids.len() {
+        let current = ids[index];
+        ids.extend(
+            folders
+                .iter()
+                .filter(|f| f.parent_id == Some(current) && !ids.contains(&f.id))
+                .map(|f| f.id)
+                .collect::<Vec<i64>>(),
+        );
+        index += 1;
+    }
+    ids
+}
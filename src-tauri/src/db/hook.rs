@@ -0,0 +1,126 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite};
+
+/// 用户钩子: 事件发生时执行的命令
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct Hook {
+    pub id: i64,
+    /// 事件名称,如`launcher:launched`
+    pub event: String,
+    /// 使用系统shell执行的命令
+    pub command: String,
+    /// 超时时间(秒),超时后结束命令
+    pub timeout_secs: i64,
+    pub enabled: bool,
+}
+
+pub async fn initialize<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS hook(
+                id           INTEGER PRIMARY KEY NOT NULL,
+                event        VARCHAR             NOT NULL,
+                command      VARCHAR             NOT NULL,
+                timeout_secs INTEGER             NOT NULL DEFAULT 30,
+                enabled      INTEGER             NOT NULL DEFAULT 1);
+                CREATE INDEX IF NOT EXISTS idx_hook_event ON hook(event);"#,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 新增钩子
+pub async fn create<'a, E>(
+    executor: E,
+    event: &str,
+    command: &str,
+    timeout_secs: i64,
+) -> Result<i64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query("INSERT INTO hook (event,command,timeout_secs) VALUES (?,?,?)")
+        .bind(event)
+        .bind(command)
+        .bind(timeout_secs)
+        .execute(executor)
+        .await?
+        .last_insert_rowid();
+    Ok(id)
+}
+
+/// 修改钩子
+pub async fn modify<'a, E>(
+    executor: E,
+    id: i64,
+    command: &str,
+    timeout_secs: i64,
+    enabled: bool,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE hook SET command = ?, timeout_secs = ?, enabled = ? WHERE id = ?")
+        .bind(command)
+        .bind(timeout_secs)
+        .bind(enabled)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 删除钩子
+pub async fn delete<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM hook WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 按id查询
+pub async fn find_by_id<'a, E>(executor: E, id: i64) -> Result<Hook>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let hook =
+        sqlx::query_as("SELECT id,event,command,timeout_secs,enabled FROM hook WHERE id = ?")
+            .bind(id)
+            .fetch_one(executor)
+            .await?;
+    Ok(hook)
+}
+
+/// 查询全部钩子
+pub async fn query_all<'a, E>(executor: E) -> Result<Vec<Hook>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let hooks = sqlx::query_as(
+        "SELECT id,event,command,timeout_secs,enabled FROM hook ORDER BY event ASC, id ASC",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(hooks)
+}
+
+/// 查询事件上启用的钩子,按添加顺序排列
+pub async fn query_enabled_by_event<'a, E>(executor: E, event: &str) -> Result<Vec<Hook>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let hooks = sqlx::query_as(
+        "SELECT id,event,command,timeout_secs,enabled FROM hook WHERE event = ? AND enabled = 1 ORDER BY id ASC",
+    )
+    .bind(event)
+    .fetch_all(executor)
+    .await?;
+    Ok(hooks)
+}
@@ -1,5 +1,9 @@
 use anyhow::Result;
-use sqlx::{Executor, Sqlite};
+use sqlx::{Executor, Sqlite, SqlitePool};
+
+use uuid::Uuid;
+
+use super::{add_column_if_absent, initialize_sync_columns};
 
 /// 使用 FromRow 派生宏把从数据库中读取出来的数据转换成 Launcher 结构
 #[allow(dead_code)]
@@ -8,20 +12,65 @@ pub struct Launcher {
     pub id: i64,
     pub name: String,
     pub sort: i32,
+    pub folder_id: Option<i64>,
+    pub notes: String,
+    /// 启动前执行的命令,为空表示不执行
+    pub pre_launch_command: String,
+    /// 启动后执行的命令,为空表示不执行
+    pub post_launch_command: String,
+    /// 别名,命令行与快捷方式使用别名指定启动器,导入导出后不变
+    pub slug: Option<String>,
 }
 
-pub async fn initialize<'a, E>(executor: E) -> Result<()>
-where
-    E: Executor<'a, Database = Sqlite>,
-{
+/// 回收站中的启动器
+#[derive(sqlx::FromRow, Debug)]
+pub struct DeletedLauncher {
+    pub id: i64,
+    pub name: String,
+    pub deleted_at: i64,
+}
+
+pub async fn initialize(pool: &SqlitePool) -> Result<()> {
     sqlx::query(
         r#"CREATE TABLE IF NOT EXISTS launcher(
                 id          INTEGER PRIMARY KEY NOT NULL,
                 name        VARCHAR             NOT NULL,
                 sort        INTEGER             NOT NULL DEFAULT 1)"#,
     )
-    .execute(executor)
+    .execute(pool)
     .await?;
+
+    // 所属文件夹,为空表示位于根目录
+    add_column_if_absent(pool, "launcher", "folder_id", "INTEGER").await?;
+
+    // 删除时间(Unix秒),不为空表示已被移入回收站
+    add_column_if_absent(pool, "launcher", "deleted_at", "INTEGER").await?;
+
+    // 备注
+    add_column_if_absent(pool, "launcher", "notes", "VARCHAR NOT NULL DEFAULT ''").await?;
+
+    // 启动前后执行的命令
+    add_column_if_absent(
+        pool,
+        "launcher",
+        "pre_launch_command",
+        "VARCHAR NOT NULL DEFAULT ''",
+    )
+    .await?;
+    add_column_if_absent(
+        pool,
+        "launcher",
+        "post_launch_command",
+        "VARCHAR NOT NULL DEFAULT ''",
+    )
+    .await?;
+
+    // 别名,为空表示未设置
+    add_column_if_absent(pool, "launcher", "slug", "VARCHAR").await?;
+
+    // 跨设备同步使用的uuid与修改时间
+    initialize_sync_columns(pool, "launcher").await?;
+
     Ok(())
 }
 
@@ -30,9 +79,10 @@ pub async fn create<'a, E>(executor: E, launcher_name: &str, sort: Option<i32>)
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    let id = sqlx::query("INSERT INTO launcher (name,sort) VALUES (?,?)")
+    let id = sqlx::query("INSERT INTO launcher (name,sort,uuid) VALUES (?,?,?)")
         .bind(launcher_name)
         .bind(sort.unwrap_or_default())
+        .bind(Uuid::new_v4().to_string())
         .execute(executor)
         .await?
         .last_insert_rowid();
@@ -52,6 +102,19 @@ where
     Ok(())
 }
 
+/// 修改启动器别名,`slug`为空时清除别名
+pub async fn modify_launcher_slug<'a, E>(executor: E, id: i64, slug: Option<&str>) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher SET slug = ? WHERE id = ?")
+        .bind(slug)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
 /// 修改启动器顺序
 pub async fn modify_launcher_sort<'a, E>(executor: E, id: i64, sort: i32) -> Result<()>
 where
@@ -65,25 +128,171 @@ where
     Ok(())
 }
 
-/// 删除启动器
+/// 修改启动器备注
+pub async fn modify_launcher_notes<'a, E>(executor: E, id: i64, notes: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher SET notes = ? WHERE id = ?")
+        .bind(notes)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 修改启动器启动前后执行的命令
+pub async fn modify_launch_commands<'a, E>(
+    executor: E,
+    id: i64,
+    pre_launch_command: &str,
+    post_launch_command: &str,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher SET pre_launch_command = ?, post_launch_command = ? WHERE id = ?")
+        .bind(pre_launch_command)
+        .bind(post_launch_command)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 修改启动器所属文件夹
+pub async fn modify_launcher_folder<'a, E>(
+    executor: E,
+    id: i64,
+    folder_id: Option<i64>,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher SET folder_id = ? WHERE id = ?")
+        .bind(folder_id)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 将指定文件夹下的启动器全部移动到另一个文件夹
+pub async fn move_folder_launchers<'a, E>(
+    executor: E,
+    folder_id: i64,
+    target_folder_id: Option<i64>,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher SET folder_id = ? WHERE folder_id = ?")
+        .bind(target_folder_id)
+        .bind(folder_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 删除启动器,启动器会被移入回收站
 pub async fn delete_by_id<'a, E>(executor: E, id: i64) -> Result<()>
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    sqlx::query("DELETE FROM launcher WHERE id = ?")
+    sqlx::query("UPDATE launcher SET deleted_at = strftime('%s','now') WHERE id = ?")
         .bind(id)
         .execute(executor)
         .await?;
     Ok(())
 }
 
+/// 从回收站恢复启动器,如果原所属文件夹已不存在则恢复到根目录
+///
+/// 别名在删除期间被其他启动器使用时清除恢复的启动器的别名
+pub async fn restore_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"UPDATE launcher SET deleted_at = NULL,
+            folder_id = CASE WHEN folder_id IN (SELECT id FROM folder) THEN folder_id ELSE NULL END,
+            slug = CASE WHEN EXISTS (
+                SELECT 1 FROM launcher other
+                WHERE other.slug = launcher.slug AND other.id != launcher.id AND other.deleted_at IS NULL
+            ) THEN NULL ELSE slug END
+            WHERE id = ?"#,
+    )
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 启动器的别名与其他未删除的启动器重复时清除该启动器的别名
+pub async fn clear_duplicate_slug<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"UPDATE launcher SET slug = NULL
+            WHERE id = ? AND deleted_at IS NULL AND EXISTS (
+                SELECT 1 FROM launcher other
+                WHERE other.slug = launcher.slug AND other.id != launcher.id AND other.deleted_at IS NULL
+            )"#,
+    )
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 彻底删除回收站中的启动器,返回是否有启动器被删除
+pub async fn purge_by_id<'a, E>(executor: E, id: i64) -> Result<bool>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let rows = sqlx::query("DELETE FROM launcher WHERE id = ? AND deleted_at IS NOT NULL")
+        .bind(id)
+        .execute(executor)
+        .await?
+        .rows_affected();
+    Ok(rows > 0)
+}
+
+/// 查询回收站中的启动器
+pub async fn query_deleted<'a, E>(executor: E) -> Result<Vec<DeletedLauncher>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let launchers = sqlx::query_as(
+        "SELECT id,name,deleted_at FROM launcher WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(launchers)
+}
+
+/// 查询在回收站中超过保留时长的启动器id
+pub async fn query_expired_ids<'a, E>(executor: E, retention_secs: i64) -> Result<Vec<i64>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let ids: Vec<(i64,)> = sqlx::query_as(
+        "SELECT id FROM launcher WHERE deleted_at IS NOT NULL AND deleted_at < strftime('%s','now') - ?",
+    )
+    .bind(retention_secs)
+    .fetch_all(executor)
+    .await?;
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
 /// 查询启动器列表
 pub async fn query<'a, E>(executor: E) -> Result<Vec<Launcher>>
 where
     E: Executor<'a, Database = Sqlite>,
 {
     let launchers: Vec<Launcher> =
-        sqlx::query_as("SELECT id,name,sort FROM launcher ORDER BY sort ASC, id DESC")
+        sqlx::query_as("SELECT id,name,sort,folder_id,notes,pre_launch_command,post_launch_command,slug FROM launcher WHERE deleted_at IS NULL ORDER BY sort ASC, id DESC")
             .fetch_all(executor)
             .await?;
     Ok(launchers)
@@ -94,10 +303,12 @@ pub async fn find_by_id<'a, E>(executor: E, id: i64) -> Result<Launcher>
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    let launcher = sqlx::query_as("SELECT id,name,sort FROM launcher WHERE id = ?")
-        .bind(id)
-        .fetch_one(executor)
-        .await?;
+    let launcher = sqlx::query_as(
+        "SELECT id,name,sort,folder_id,notes,pre_launch_command,post_launch_command,slug FROM launcher WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
 
     Ok(launcher)
 }
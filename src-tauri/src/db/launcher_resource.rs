@@ -1,10 +1,13 @@
 use anyhow::Result;
-use itertools::Itertools;
-use sqlx::{Executor, Sqlite};
+use sqlx::{Executor, Sqlite, SqliteConnection, SqlitePool};
+
+use uuid::Uuid;
+
+use super::{add_column_if_absent, initialize_sync_columns};
 
 /// 使用FromRow宏把数据库中读取出来的数据转换成LauncherResource结构
 #[allow(dead_code)]
-#[derive(sqlx::FromRow, Debug)]
+#[derive(sqlx::FromRow, Debug, Clone)]
 pub struct LauncherResource {
     pub id: i64,
     pub launcher_id: i64,
@@ -12,15 +15,23 @@ pub struct LauncherResource {
     pub path: String,
 }
 
+/// 回收站中的资源
+#[derive(sqlx::FromRow, Debug)]
+pub struct DeletedResource {
+    pub id: i64,
+    pub launcher_id: i64,
+    pub launcher_name: String,
+    pub name: String,
+    pub path: String,
+    pub deleted_at: i64,
+}
+
 pub struct CreateResourceParam {
     pub name: String,
     pub path: String,
 }
 
-pub async fn initialize<'a, E>(executor: E) -> Result<()>
-where
-    E: Executor<'a, Database = Sqlite>,
-{
+pub async fn initialize(pool: &SqlitePool) -> Result<()> {
     sqlx::query(
         r#"CREATE TABLE IF NOT EXISTS launcher_resource(
             id          INTEGER PRIMARY KEY NOT NULL,
@@ -29,8 +40,15 @@ where
             path        VARCHAR             NOT NULL);
             CREATE INDEX IF NOT EXISTS idx_launcherresource_launcherid ON launcher_resource(launcher_id);"#,
     )
-    .execute(executor)
+    .execute(pool)
     .await?;
+
+    // 删除时间(Unix秒),不为空表示已被移入回收站
+    add_column_if_absent(pool, "launcher_resource", "deleted_at", "INTEGER").await?;
+
+    // 跨设备同步使用的uuid与修改时间
+    initialize_sync_columns(pool, "launcher_resource").await?;
+
     Ok(())
 }
 
@@ -39,37 +57,31 @@ pub async fn create<'a, E>(executor: E, launcher_id: i64, name: &str, path: &str
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    let id = sqlx::query("INSERT INTO launcher_resource (launcher_id,name,path) VALUES (?,?,?)")
-        .bind(launcher_id)
-        .bind(name)
-        .bind(path)
-        .execute(executor)
-        .await?
-        .last_insert_rowid();
+    let id =
+        sqlx::query("INSERT INTO launcher_resource (launcher_id,name,path,uuid) VALUES (?,?,?,?)")
+            .bind(launcher_id)
+            .bind(name)
+            .bind(path)
+            .bind(Uuid::new_v4().to_string())
+            .execute(executor)
+            .await?
+            .last_insert_rowid();
     Ok(id)
 }
 
 // 批量新增
-pub async fn create_resources<'a, E>(
-    executor: E,
+pub async fn create_resources(
+    conn: &mut SqliteConnection,
     launcher_id: i64,
     resources: &[CreateResourceParam],
-) -> Result<()>
-where
-    E: Executor<'a, Database = Sqlite>,
-{
-    let mut query = String::from("INSERT INTO launcher_resource (launcher_id,name,path) VALUES ");
-
-    let values = resources
-        .iter()
-        .map(|r| format!(r#"('{}','{}','{}')"#, launcher_id, r.name, r.path))
-        .join(",");
-
-    query.push_str(values.as_str());
+) -> Result<Vec<i64>> {
+    let mut ids = Vec::with_capacity(resources.len());
 
-    sqlx::query(&query).execute(executor).await?;
+    for resource in resources.iter() {
+        ids.push(create(&mut *conn, launcher_id, &resource.name, &resource.path).await?);
+    }
 
-    Ok(())
+    Ok(ids)
 }
 
 // 修改名称
@@ -98,7 +110,7 @@ where
     Ok(())
 }
 
-// 按launcher_id删除
+// 按launcher_id彻底删除
 pub async fn delete_by_launcher<'a, E>(executor: E, launcher_id: i64) -> Result<()>
 where
     E: Executor<'a, Database = Sqlite>,
@@ -110,18 +122,87 @@ where
     Ok(())
 }
 
-// 按id删除
+// 按id删除,资源会被移入回收站
 pub async fn delete_by_id<'a, E>(executor: E, id: i64) -> Result<()>
 where
     E: Executor<'a, Database = Sqlite>,
 {
-    sqlx::query("DELETE FROM launcher_resource WHERE id = ?")
+    sqlx::query("UPDATE launcher_resource SET deleted_at = strftime('%s','now') WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+// 按id从回收站恢复
+pub async fn restore_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE launcher_resource SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+// 按id彻底删除回收站中的资源
+pub async fn purge_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM launcher_resource WHERE id = ? AND deleted_at IS NOT NULL")
         .bind(id)
         .execute(executor)
         .await?;
     Ok(())
 }
 
+// 彻底删除在回收站中超过保留时长的资源
+pub async fn purge_expired<'a, E>(executor: E, retention_secs: i64) -> Result<u64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let rows = sqlx::query(
+        "DELETE FROM launcher_resource WHERE deleted_at IS NOT NULL AND deleted_at < strftime('%s','now') - ?",
+    )
+    .bind(retention_secs)
+    .execute(executor)
+    .await?
+    .rows_affected();
+    Ok(rows)
+}
+
+// 查询回收站中的资源,不包含所属启动器已被删除的资源
+pub async fn query_deleted<'a, E>(executor: E) -> Result<Vec<DeletedResource>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let resources = sqlx::query_as(
+        r#"SELECT r.id, r.launcher_id, l.name AS launcher_name, r.name, r.path, r.deleted_at
+            FROM launcher_resource r INNER JOIN launcher l ON l.id = r.launcher_id
+            WHERE r.deleted_at IS NOT NULL AND l.deleted_at IS NULL
+            ORDER BY r.deleted_at DESC"#,
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(resources)
+}
+
+// 按id查询
+pub async fn find_by_id<'a, E>(executor: E, id: i64) -> Result<LauncherResource>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let resource = sqlx::query_as(
+        "SELECT id,launcher_id,name,path FROM launcher_resource WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_one(executor)
+    .await?;
+    Ok(resource)
+}
+
 // 按launcher_id查询
 pub async fn query_by_launcher_id<'a, E>(
     executor: E,
@@ -131,7 +212,7 @@ where
     E: Executor<'a, Database = Sqlite>,
 {
     let resources = sqlx::query_as(
-        "SELECT id,launcher_id,name,path FROM launcher_resource WHERE launcher_id=?",
+        "SELECT id,launcher_id,name,path FROM launcher_resource WHERE launcher_id=? AND deleted_at IS NULL",
     )
     .bind(launcher_id)
     .fetch_all(executor)
@@ -157,7 +238,33 @@ where
         .collect::<Vec<String>>()
         .join(",");
     let query = format!(
-        "SELECT id, launcher_id, name, path FROM launcher_resource WHERE launcher_id IN ({})",
+        r#"SELECT id, launcher_id, name, path FROM launcher_resource
+            WHERE launcher_id IN ({}) AND deleted_at IS NULL
+            AND launcher_id IN (SELECT id FROM launcher WHERE deleted_at IS NULL)"#,
+        ids
+    );
+
+    let resources = sqlx::query_as(&query).fetch_all(executor).await?;
+
+    Ok(resources)
+}
+
+// 按id查询多个资源
+pub async fn query_by_ids<'a, E>(executor: E, ids: &[i64]) -> Result<Vec<LauncherResource>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let ids = ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    let query = format!(
+        "SELECT id,launcher_id,name,path FROM launcher_resource WHERE id IN ({}) AND deleted_at IS NULL",
         ids
     );
 
@@ -172,7 +279,7 @@ where
     E: Executor<'a, Database = Sqlite>,
 {
     let resources =
-        sqlx::query_as("SELECT id,launcher_id,name,path FROM launcher_resource ORDER BY id DESC")
+        sqlx::query_as("SELECT id,launcher_id,name,path FROM launcher_resource WHERE deleted_at IS NULL ORDER BY id DESC")
             .fetch_all(executor)
             .await?;
     Ok(resources)
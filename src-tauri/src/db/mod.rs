@@ -1,3 +1,86 @@
+pub mod folder;
+pub mod hook;
 pub mod launcher;
 pub mod launcher_resource;
+pub mod resource_path_override;
+pub mod search_index;
 pub mod settings;
+pub mod sync;
+pub mod tag;
+pub mod undo_journal;
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// 当前时间(Unix毫秒)的SQL表达式
+pub const NOW_MILLIS_SQL: &str = "CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER)";
+
+/// 为已存在的表补充新增的列,用于兼容旧版本创建的数据库
+pub async fn add_column_if_absent(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let columns: Vec<(String,)> =
+        sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+            .fetch_all(pool)
+            .await?;
+
+    if !columns.iter().any(|(name,)| name == column) {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 为表补充跨设备同步所需的`uuid`与`updated_at`列,并为缺少uuid的旧数据生成uuid
+///
+/// `updated_at`由触发器维护: 新增或修改记录时自动更新为当前时间,
+/// 修改时显式指定了新的`updated_at`(如同步导入)则保留指定的值
+pub async fn initialize_sync_columns(pool: &SqlitePool, table: &str) -> Result<()> {
+    add_column_if_absent(pool, table, "uuid", "VARCHAR").await?;
+
+    add_column_if_absent(pool, table, "updated_at", "INTEGER NOT NULL DEFAULT 0").await?;
+
+    let ids: Vec<(i64,)> = sqlx::query_as(&format!("SELECT id FROM {} WHERE uuid IS NULL", table))
+        .fetch_all(pool)
+        .await?;
+
+    for (id,) in ids {
+        sqlx::query(&format!("UPDATE {} SET uuid = ? WHERE id = ?", table))
+            .bind(Uuid::new_v4().to_string())
+            .bind(id)
+            .execute(pool)
+            .await?;
+    }
+
+    sqlx::query(&format!(
+        r#"CREATE UNIQUE INDEX IF NOT EXISTS idx_{table}_uuid ON {table}(uuid);
+            UPDATE {table} SET updated_at = {now} WHERE updated_at = 0;
+            DROP TRIGGER IF EXISTS trg_{table}_touch_insert;
+            DROP TRIGGER IF EXISTS trg_{table}_touch_update;
+            CREATE TRIGGER trg_{table}_touch_insert AFTER INSERT ON {table}
+            WHEN NEW.updated_at = 0
+            BEGIN
+                UPDATE {table} SET updated_at = {now} WHERE id = NEW.id;
+            END;
+            CREATE TRIGGER trg_{table}_touch_update AFTER UPDATE ON {table}
+            WHEN NEW.updated_at = OLD.updated_at
+            BEGIN
+                UPDATE {table} SET updated_at = {now} WHERE id = NEW.id;
+            END;"#,
+        table = table,
+        now = NOW_MILLIS_SQL
+    ))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite, SqlitePool};
+
+use super::NOW_MILLIS_SQL;
+
+/// 资源在指定主机上使用的路径
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ResourcePathOverride {
+    pub resource_id: i64,
+    pub hostname: String,
+    pub path: String,
+}
+
+pub async fn initialize(pool: &SqlitePool) -> Result<()> {
+    // 主机名不区分大小写; 主机路径变更时同步更新资源的修改时间,资源被彻底删除时一并删除
+    sqlx::query(&format!(
+        r#"CREATE TABLE IF NOT EXISTS resource_path_override(
+                resource_id INTEGER NOT NULL,
+                hostname    VARCHAR NOT NULL COLLATE NOCASE,
+                path        VARCHAR NOT NULL,
+                PRIMARY KEY (resource_id, hostname));
+            DROP TRIGGER IF EXISTS trg_resource_path_override_insert;
+            DROP TRIGGER IF EXISTS trg_resource_path_override_update;
+            DROP TRIGGER IF EXISTS trg_resource_path_override_delete;
+            DROP TRIGGER IF EXISTS trg_resource_path_override_cleanup;
+            CREATE TRIGGER trg_resource_path_override_insert AFTER INSERT ON resource_path_override
+            BEGIN
+                UPDATE launcher_resource SET updated_at = {now} WHERE id = NEW.resource_id;
+            END;
+            CREATE TRIGGER trg_resource_path_override_update AFTER UPDATE ON resource_path_override
+            BEGIN
+                UPDATE launcher_resource SET updated_at = {now} WHERE id = NEW.resource_id;
+            END;
+            CREATE TRIGGER trg_resource_path_override_delete AFTER DELETE ON resource_path_override
+            BEGIN
+                UPDATE launcher_resource SET updated_at = {now} WHERE id = OLD.resource_id;
+            END;
+            CREATE TRIGGER trg_resource_path_override_cleanup AFTER DELETE ON launcher_resource
+            BEGIN
+                DELETE FROM resource_path_override WHERE resource_id = OLD.id;
+            END;"#,
+        now = NOW_MILLIS_SQL
+    ))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 设置资源在指定主机上使用的路径
+pub async fn save<'a, E>(executor: E, resource_id: i64, hostname: &str, path: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"INSERT INTO resource_path_override (resource_id,hostname,path) VALUES (?,?,?)
+            ON CONFLICT(resource_id,hostname) DO UPDATE SET path = excluded.path"#,
+    )
+    .bind(resource_id)
+    .bind(hostname)
+    .bind(path)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 删除资源在指定主机上的路径
+pub async fn delete<'a, E>(executor: E, resource_id: i64, hostname: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM resource_path_override WHERE resource_id = ? AND hostname = ?")
+        .bind(resource_id)
+        .bind(hostname)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 删除资源在全部主机上的路径
+pub async fn delete_by_resource<'a, E>(executor: E, resource_id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM resource_path_override WHERE resource_id = ?")
+        .bind(resource_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 查询资源在指定主机上的路径
+pub async fn find<'a, E>(
+    executor: E,
+    resource_id: i64,
+    hostname: &str,
+) -> Result<Option<ResourcePathOverride>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let path_override = sqlx::query_as(
+        "SELECT resource_id,hostname,path FROM resource_path_override WHERE resource_id = ? AND hostname = ?",
+    )
+    .bind(resource_id)
+    .bind(hostname)
+    .fetch_optional(executor)
+    .await?;
+    Ok(path_override)
+}
+
+/// 查询全部主机路径
+pub async fn query_all<'a, E>(executor: E) -> Result<Vec<ResourcePathOverride>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let overrides = sqlx::query_as(
+        "SELECT resource_id,hostname,path FROM resource_path_override ORDER BY hostname ASC",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(overrides)
+}
+
+/// 查询指定主机上的全部路径
+pub async fn query_by_hostname<'a, E>(
+    executor: E,
+    hostname: &str,
+) -> Result<Vec<ResourcePathOverride>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let overrides = sqlx::query_as(
+        "SELECT resource_id,hostname,path FROM resource_path_override WHERE hostname = ?",
+    )
+    .bind(hostname)
+    .fetch_all(executor)
+    .await?;
+    Ok(overrides)
+}
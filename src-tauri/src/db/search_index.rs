@@ -0,0 +1,177 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite, SqlitePool};
+
+use crate::constants::SCHEMA_VERSION;
+
+/// 使用FromRow宏把全文检索结果转换成SearchHit结构
+#[derive(sqlx::FromRow, Debug)]
+pub struct SearchHit {
+    /// 命中的记录类型: launcher / resource
+    pub kind: String,
+    pub item_id: i64,
+    pub launcher_id: i64,
+    pub name: String,
+    pub path: String,
+    pub tags: String,
+    pub notes: String,
+    /// bm25相关度,越小越相关
+    pub rank: f64,
+}
+
+/// 创建全文检索表与同步触发器,数据库结构升级时根据现有数据重建索引
+///
+/// 索引表由`launcher`、`launcher_resource`、`launcher_tag`与`tag`上的触发器维护,
+/// 资源的启动参数包含在`path`列中一并检索;`user_version`为升级前的数据库结构版本
+pub async fn initialize(pool: &SqlitePool, user_version: i64) -> Result<()> {
+    sqlx::query(
+        r#"CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                kind UNINDEXED,
+                item_id UNINDEXED,
+                launcher_id UNINDEXED,
+                name,
+                path,
+                tags,
+                notes,
+                tokenize = 'unicode61 remove_diacritics 2');"#,
+    )
+    .execute(pool)
+    .await?;
+
+    // 触发器定义可能随版本变化,每次启动时重新创建
+    sqlx::query(
+        r#"DROP TRIGGER IF EXISTS trg_search_launcher_insert;
+            DROP TRIGGER IF EXISTS trg_search_launcher_update;
+            DROP TRIGGER IF EXISTS trg_search_launcher_delete;
+            DROP TRIGGER IF EXISTS trg_search_resource_insert;
+            DROP TRIGGER IF EXISTS trg_search_resource_update;
+            DROP TRIGGER IF EXISTS trg_search_resource_delete;
+            DROP TRIGGER IF EXISTS trg_search_launcher_tag_insert;
+            DROP TRIGGER IF EXISTS trg_search_launcher_tag_delete;
+            DROP TRIGGER IF EXISTS trg_search_tag_update;
+
+            CREATE TRIGGER trg_search_launcher_insert AFTER INSERT ON launcher
+            WHEN NEW.deleted_at IS NULL
+            BEGIN
+                INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+                VALUES ('launcher', NEW.id, NEW.id, NEW.name, '', '', NEW.notes);
+            END;
+
+            CREATE TRIGGER trg_search_launcher_update AFTER UPDATE ON launcher
+            BEGIN
+                DELETE FROM search_index WHERE kind = 'launcher' AND item_id = OLD.id;
+                INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+                SELECT 'launcher', NEW.id, NEW.id, NEW.name, '',
+                    IFNULL((SELECT group_concat(t.name, ' ') FROM launcher_tag lt
+                        INNER JOIN tag t ON t.id = lt.tag_id WHERE lt.launcher_id = NEW.id), ''),
+                    NEW.notes
+                WHERE NEW.deleted_at IS NULL;
+            END;
+
+            CREATE TRIGGER trg_search_launcher_delete AFTER DELETE ON launcher
+            BEGIN
+                DELETE FROM search_index WHERE kind = 'launcher' AND item_id = OLD.id;
+            END;
+
+            CREATE TRIGGER trg_search_resource_insert AFTER INSERT ON launcher_resource
+            WHEN NEW.deleted_at IS NULL
+            BEGIN
+                INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+                VALUES ('resource', NEW.id, NEW.launcher_id, NEW.name, NEW.path, '', '');
+            END;
+
+            CREATE TRIGGER trg_search_resource_update AFTER UPDATE ON launcher_resource
+            BEGIN
+                DELETE FROM search_index WHERE kind = 'resource' AND item_id = OLD.id;
+                INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+                SELECT 'resource', NEW.id, NEW.launcher_id, NEW.name, NEW.path, '', ''
+                WHERE NEW.deleted_at IS NULL;
+            END;
+
+            CREATE TRIGGER trg_search_resource_delete AFTER DELETE ON launcher_resource
+            BEGIN
+                DELETE FROM search_index WHERE kind = 'resource' AND item_id = OLD.id;
+            END;
+
+            CREATE TRIGGER trg_search_launcher_tag_insert AFTER INSERT ON launcher_tag
+            BEGIN
+                UPDATE search_index SET tags =
+                    IFNULL((SELECT group_concat(t.name, ' ') FROM launcher_tag lt
+                        INNER JOIN tag t ON t.id = lt.tag_id WHERE lt.launcher_id = NEW.launcher_id), '')
+                WHERE kind = 'launcher' AND item_id = NEW.launcher_id;
+            END;
+
+            CREATE TRIGGER trg_search_launcher_tag_delete AFTER DELETE ON launcher_tag
+            BEGIN
+                UPDATE search_index SET tags =
+                    IFNULL((SELECT group_concat(t.name, ' ') FROM launcher_tag lt
+                        INNER JOIN tag t ON t.id = lt.tag_id WHERE lt.launcher_id = OLD.launcher_id), '')
+                WHERE kind = 'launcher' AND item_id = OLD.launcher_id;
+            END;
+
+            CREATE TRIGGER trg_search_tag_update AFTER UPDATE OF name ON tag
+            BEGIN
+                UPDATE search_index SET tags =
+                    IFNULL((SELECT group_concat(t.name, ' ') FROM launcher_tag lt
+                        INNER JOIN tag t ON t.id = lt.tag_id WHERE lt.launcher_id = search_index.item_id), '')
+                WHERE kind = 'launcher'
+                    AND item_id IN (SELECT launcher_id FROM launcher_tag WHERE tag_id = NEW.id);
+            END;"#,
+    )
+    .execute(pool)
+    .await?;
+
+    if user_version < SCHEMA_VERSION {
+        rebuild(pool).await?;
+    }
+
+    Ok(())
+}
+
+/// 根据现有数据重建索引
+pub async fn rebuild<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"DELETE FROM search_index;
+            INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+            SELECT 'launcher', l.id, l.id, l.name, '',
+                IFNULL((SELECT group_concat(t.name, ' ') FROM launcher_tag lt
+                    INNER JOIN tag t ON t.id = lt.tag_id WHERE lt.launcher_id = l.id), ''),
+                l.notes
+            FROM launcher l WHERE l.deleted_at IS NULL;
+            INSERT INTO search_index (kind,item_id,launcher_id,name,path,tags,notes)
+            SELECT 'resource', r.id, r.launcher_id, r.name, r.path, '', ''
+            FROM launcher_resource r WHERE r.deleted_at IS NULL;"#,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 全文检索,`query`为FTS5查询表达式,命中的内容使用`<mark>`标签包裹
+pub async fn search<'a, E>(executor: E, query: &str, limit: i64) -> Result<Vec<SearchHit>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let hits = sqlx::query_as(
+        r#"SELECT kind,
+                CAST(item_id AS INTEGER) AS item_id,
+                CAST(launcher_id AS INTEGER) AS launcher_id,
+                highlight(search_index, 3, '<mark>', '</mark>') AS name,
+                highlight(search_index, 4, '<mark>', '</mark>') AS path,
+                highlight(search_index, 5, '<mark>', '</mark>') AS tags,
+                highlight(search_index, 6, '<mark>', '</mark>') AS notes,
+                rank
+            FROM search_index
+            WHERE search_index MATCH ?
+                AND launcher_id IN (SELECT id FROM launcher WHERE deleted_at IS NULL)
+            ORDER BY rank
+            LIMIT ?"#,
+    )
+    .bind(query)
+    .bind(limit)
+    .fetch_all(executor)
+    .await?;
+    Ok(hits)
+}
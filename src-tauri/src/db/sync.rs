@@ -0,0 +1,324 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite, SqlitePool};
+
+use super::NOW_MILLIS_SQL;
+
+/// 同步记录类型: 启动器
+pub const LAUNCHER_KIND: &str = "launcher";
+/// 同步记录类型: 资源
+pub const RESOURCE_KIND: &str = "resource";
+
+/// 同步使用的启动器数据,包含回收站中的启动器
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct SyncLauncher {
+    pub id: i64,
+    pub uuid: String,
+    pub name: String,
+    pub sort: i32,
+    pub folder_id: Option<i64>,
+    pub notes: String,
+    pub pre_launch_command: String,
+    pub post_launch_command: String,
+    pub slug: Option<String>,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+/// 同步使用的资源数据,包含回收站中的资源
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct SyncResource {
+    pub id: i64,
+    pub uuid: String,
+    pub launcher_id: i64,
+    pub name: String,
+    pub path: String,
+    pub updated_at: i64,
+    pub deleted_at: Option<i64>,
+}
+
+/// 已彻底删除的记录,用于把删除同步到其他设备
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct Tombstone {
+    pub uuid: String,
+    pub kind: String,
+    pub deleted_at: i64,
+}
+
+/// 同步冲突记录
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct SyncConflict {
+    pub id: i64,
+    pub kind: String,
+    pub uuid: String,
+    pub local_value: String,
+    pub remote_value: String,
+    pub winner: String,
+    pub remote_machine: String,
+    pub created_at: i64,
+}
+
+pub async fn initialize(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(&format!(
+        r#"CREATE TABLE IF NOT EXISTS sync_tombstone(
+                uuid        VARCHAR PRIMARY KEY NOT NULL,
+                kind        VARCHAR             NOT NULL,
+                deleted_at  INTEGER             NOT NULL);
+            CREATE TABLE IF NOT EXISTS sync_state(
+                machine_id          VARCHAR PRIMARY KEY NOT NULL,
+                file_modified_at    INTEGER             NOT NULL,
+                imported_at         INTEGER             NOT NULL);
+            CREATE TABLE IF NOT EXISTS sync_conflict(
+                id              INTEGER PRIMARY KEY NOT NULL,
+                kind            VARCHAR             NOT NULL,
+                uuid            VARCHAR             NOT NULL,
+                local_value     VARCHAR             NOT NULL,
+                remote_value    VARCHAR             NOT NULL,
+                winner          VARCHAR             NOT NULL,
+                remote_machine  VARCHAR             NOT NULL,
+                created_at      INTEGER             NOT NULL);
+            DROP TRIGGER IF EXISTS trg_sync_launcher_delete;
+            DROP TRIGGER IF EXISTS trg_sync_resource_delete;
+            DROP TRIGGER IF EXISTS trg_sync_launcher_tag_insert;
+            DROP TRIGGER IF EXISTS trg_sync_launcher_tag_delete;
+            DROP TRIGGER IF EXISTS trg_sync_folder_update;
+            CREATE TRIGGER trg_sync_launcher_delete AFTER DELETE ON launcher
+            WHEN OLD.uuid IS NOT NULL
+            BEGIN
+                INSERT OR REPLACE INTO sync_tombstone (uuid,kind,deleted_at)
+                VALUES (OLD.uuid, '{launcher}', {now});
+            END;
+            CREATE TRIGGER trg_sync_resource_delete AFTER DELETE ON launcher_resource
+            WHEN OLD.uuid IS NOT NULL
+            BEGIN
+                INSERT OR REPLACE INTO sync_tombstone (uuid,kind,deleted_at)
+                VALUES (OLD.uuid, '{resource}', {now});
+            END;
+            CREATE TRIGGER trg_sync_launcher_tag_insert AFTER INSERT ON launcher_tag
+            BEGIN
+                UPDATE launcher SET updated_at = {now} WHERE id = NEW.launcher_id;
+            END;
+            CREATE TRIGGER trg_sync_launcher_tag_delete AFTER DELETE ON launcher_tag
+            BEGIN
+                UPDATE launcher SET updated_at = {now} WHERE id = OLD.launcher_id;
+            END;
+            CREATE TRIGGER trg_sync_folder_update AFTER UPDATE OF name, parent_id ON folder
+            BEGIN
+                UPDATE launcher SET updated_at = {now} WHERE folder_id = NEW.id;
+            END;"#,
+        launcher = LAUNCHER_KIND,
+        resource = RESOURCE_KIND,
+        now = NOW_MILLIS_SQL
+    ))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// 查询全部启动器,包含回收站中的启动器
+pub async fn query_launchers<'a, E>(executor: E) -> Result<Vec<SyncLauncher>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let launchers = sqlx::query_as(
+        "SELECT id,uuid,name,sort,folder_id,notes,pre_launch_command,post_launch_command,slug,updated_at,deleted_at FROM launcher",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(launchers)
+}
+
+/// 查询全部资源,包含回收站中的资源
+pub async fn query_resources<'a, E>(executor: E) -> Result<Vec<SyncResource>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let resources = sqlx::query_as(
+        "SELECT id,uuid,launcher_id,name,path,updated_at,deleted_at FROM launcher_resource ORDER BY id ASC",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(resources)
+}
+
+/// 设置记录的uuid,用于保持与其他设备上的记录一致
+pub async fn modify_uuid<'a, E>(executor: E, kind: &str, id: i64, uuid: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(&format!(
+        "UPDATE {} SET uuid = ? WHERE id = ?",
+        table_name(kind)
+    ))
+    .bind(uuid)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 设置记录的删除时间,为空表示不在回收站中
+pub async fn modify_deleted_at<'a, E>(
+    executor: E,
+    kind: &str,
+    id: i64,
+    deleted_at: Option<i64>,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(&format!(
+        "UPDATE {} SET deleted_at = ? WHERE id = ?",
+        table_name(kind)
+    ))
+    .bind(deleted_at)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 设置记录的修改时间,需要在其他修改完成后调用
+pub async fn modify_updated_at<'a, E>(
+    executor: E,
+    kind: &str,
+    id: i64,
+    updated_at: i64,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(&format!(
+        "UPDATE {} SET updated_at = ? WHERE id = ?",
+        table_name(kind)
+    ))
+    .bind(updated_at)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 查询全部删除记录
+pub async fn query_tombstones<'a, E>(executor: E) -> Result<Vec<Tombstone>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let tombstones = sqlx::query_as("SELECT uuid,kind,deleted_at FROM sync_tombstone")
+        .fetch_all(executor)
+        .await?;
+    Ok(tombstones)
+}
+
+/// 保存删除记录,已存在时保留较晚的删除时间
+pub async fn save_tombstone<'a, E>(executor: E, tombstone: &Tombstone) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"INSERT INTO sync_tombstone (uuid,kind,deleted_at) VALUES (?,?,?)
+            ON CONFLICT(uuid) DO UPDATE SET deleted_at = MAX(deleted_at, excluded.deleted_at)"#,
+    )
+    .bind(&tombstone.uuid)
+    .bind(&tombstone.kind)
+    .bind(tombstone.deleted_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 查询上次导入指定设备同步文件时,该文件的修改时间与导入时间
+pub async fn find_state<'a, E>(executor: E, machine_id: &str) -> Result<Option<(i64, i64)>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let state =
+        sqlx::query_as("SELECT file_modified_at,imported_at FROM sync_state WHERE machine_id = ?")
+            .bind(machine_id)
+            .fetch_optional(executor)
+            .await?;
+    Ok(state)
+}
+
+/// 记录导入指定设备同步文件的状态
+pub async fn save_state<'a, E>(
+    executor: E,
+    machine_id: &str,
+    file_modified_at: i64,
+    imported_at: i64,
+) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        "INSERT OR REPLACE INTO sync_state (machine_id,file_modified_at,imported_at) VALUES (?,?,?)",
+    )
+    .bind(machine_id)
+    .bind(file_modified_at)
+    .bind(imported_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 记录同步冲突
+pub async fn create_conflict<'a, E>(
+    executor: E,
+    kind: &str,
+    uuid: &str,
+    local_value: &str,
+    remote_value: &str,
+    winner: &str,
+    remote_machine: &str,
+) -> Result<i64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query(&format!(
+        r#"INSERT INTO sync_conflict (kind,uuid,local_value,remote_value,winner,remote_machine,created_at)
+            VALUES (?,?,?,?,?,?,{})"#,
+        NOW_MILLIS_SQL
+    ))
+    .bind(kind)
+    .bind(uuid)
+    .bind(local_value)
+    .bind(remote_value)
+    .bind(winner)
+    .bind(remote_machine)
+    .execute(executor)
+    .await?
+    .last_insert_rowid();
+    Ok(id)
+}
+
+/// 查询全部同步冲突,最近的在前
+pub async fn query_conflicts<'a, E>(executor: E) -> Result<Vec<SyncConflict>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let conflicts = sqlx::query_as(
+        "SELECT id,kind,uuid,local_value,remote_value,winner,remote_machine,created_at FROM sync_conflict ORDER BY id DESC",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(conflicts)
+}
+
+/// 清空同步冲突记录
+pub async fn clear_conflicts<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM sync_conflict")
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+fn table_name(kind: &str) -> &'static str {
+    if kind == LAUNCHER_KIND {
+        "launcher"
+    } else {
+        "launcher_resource"
+    }
+}
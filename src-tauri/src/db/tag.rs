@@ -0,0 +1,155 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite};
+
+/// 使用FromRow宏把数据库中读取出来的数据转换成Tag结构
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
+
+/// 启动器与标签的关联关系
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct LauncherTag {
+    pub launcher_id: i64,
+    pub tag_id: i64,
+    pub name: String,
+}
+
+pub async fn initialize<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS tag(
+                id          INTEGER PRIMARY KEY NOT NULL,
+                name        VARCHAR             NOT NULL UNIQUE);
+            CREATE TABLE IF NOT EXISTS launcher_tag(
+                launcher_id INTEGER             NOT NULL,
+                tag_id      INTEGER             NOT NULL,
+                PRIMARY KEY (launcher_id, tag_id));
+            CREATE INDEX IF NOT EXISTS idx_launchertag_tagid ON launcher_tag(tag_id);"#,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 按名称获取标签id,标签不存在时创建
+pub async fn get_or_create<'a, E>(executor: E, name: &str) -> Result<i64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let (id,): (i64,) = sqlx::query_as(
+        "INSERT INTO tag (name) VALUES (?) ON CONFLICT(name) DO UPDATE SET name = excluded.name RETURNING id",
+    )
+    .bind(name)
+    .fetch_one(executor)
+    .await?;
+    Ok(id)
+}
+
+/// 修改标签名称
+pub async fn modify_name<'a, E>(executor: E, id: i64, name: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE tag SET name = ? WHERE id = ?")
+        .bind(name)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 删除标签
+pub async fn delete_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM tag WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 查询全部标签
+pub async fn query_all<'a, E>(executor: E) -> Result<Vec<Tag>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let tags = sqlx::query_as("SELECT id,name FROM tag ORDER BY name ASC")
+        .fetch_all(executor)
+        .await?;
+    Ok(tags)
+}
+
+/// 为启动器添加标签
+pub async fn add_launcher_tag<'a, E>(executor: E, launcher_id: i64, tag_id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("INSERT OR IGNORE INTO launcher_tag (launcher_id,tag_id) VALUES (?,?)")
+        .bind(launcher_id)
+        .bind(tag_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 清除启动器的全部标签
+pub async fn delete_by_launcher<'a, E>(executor: E, launcher_id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM launcher_tag WHERE launcher_id = ?")
+        .bind(launcher_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 移除全部启动器上的指定标签
+pub async fn delete_by_tag<'a, E>(executor: E, tag_id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM launcher_tag WHERE tag_id = ?")
+        .bind(tag_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 查询全部启动器的标签
+pub async fn query_launcher_tags<'a, E>(executor: E) -> Result<Vec<LauncherTag>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let launcher_tags = sqlx::query_as(
+        r#"SELECT lt.launcher_id, lt.tag_id, t.name
+            FROM launcher_tag lt INNER JOIN tag t ON t.id = lt.tag_id
+            ORDER BY t.name ASC"#,
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(launcher_tags)
+}
+
+/// 查询单个启动器的标签
+pub async fn query_by_launcher_id<'a, E>(executor: E, launcher_id: i64) -> Result<Vec<LauncherTag>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let launcher_tags = sqlx::query_as(
+        r#"SELECT lt.launcher_id, lt.tag_id, t.name
+            FROM launcher_tag lt INNER JOIN tag t ON t.id = lt.tag_id
+            WHERE lt.launcher_id = ?
+            ORDER BY t.name ASC"#,
+    )
+    .bind(launcher_id)
+    .fetch_all(executor)
+    .await?;
+    Ok(launcher_tags)
+}
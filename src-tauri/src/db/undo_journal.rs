@@ -0,0 +1,105 @@
+use anyhow::Result;
+use sqlx::{Executor, Sqlite};
+
+/// 撤销栈
+pub static UNDO_STACK: &str = "undo";
+/// 重做栈
+pub static REDO_STACK: &str = "redo";
+
+/// 使用FromRow宏把数据库中读取出来的数据转换成UndoJournal结构
+#[derive(sqlx::FromRow, Debug)]
+pub struct UndoJournal {
+    pub id: i64,
+    pub stack: String,
+    /// 操作描述,用于在界面上展示
+    pub label: String,
+    /// 序列化后的逆操作
+    pub operation: String,
+}
+
+pub async fn initialize<'a, E>(executor: E) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS undo_journal(
+                id          INTEGER PRIMARY KEY NOT NULL,
+                stack       VARCHAR             NOT NULL,
+                label       VARCHAR             NOT NULL,
+                operation   VARCHAR             NOT NULL);
+            CREATE INDEX IF NOT EXISTS idx_undojournal_stack ON undo_journal(stack);"#,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// 压入一条记录
+pub async fn push<'a, E>(executor: E, stack: &str, label: &str, operation: &str) -> Result<i64>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query("INSERT INTO undo_journal (stack,label,operation) VALUES (?,?,?)")
+        .bind(stack)
+        .bind(label)
+        .bind(operation)
+        .execute(executor)
+        .await?
+        .last_insert_rowid();
+    Ok(id)
+}
+
+/// 查询栈顶记录
+pub async fn find_last<'a, E>(executor: E, stack: &str) -> Result<Option<UndoJournal>>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let journal = sqlx::query_as(
+        "SELECT id,stack,label,operation FROM undo_journal WHERE stack = ? ORDER BY id DESC LIMIT 1",
+    )
+    .bind(stack)
+    .fetch_optional(executor)
+    .await?;
+    Ok(journal)
+}
+
+/// 按id删除
+pub async fn delete_by_id<'a, E>(executor: E, id: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM undo_journal WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 清空栈
+pub async fn clear<'a, E>(executor: E, stack: &str) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("DELETE FROM undo_journal WHERE stack = ?")
+        .bind(stack)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 只保留栈顶的`limit`条记录
+pub async fn trim<'a, E>(executor: E, stack: &str, limit: i64) -> Result<()>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"DELETE FROM undo_journal WHERE stack = ? AND id NOT IN
+            (SELECT id FROM undo_journal WHERE stack = ? ORDER BY id DESC LIMIT ?)"#,
+    )
+    .bind(stack)
+    .bind(stack)
+    .bind(limit)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
@@ -27,6 +27,12 @@ pub enum OneClickLaunchError {
 
     #[error("{0}")]
     WindowsError(#[from] windows::core::Error),
+
+    #[error("启动器不存在: {0}")]
+    LauncherNotFound(String),
+
+    #[error("匹配到多个启动器,请使用id或更完整的名称: {0}")]
+    AmbiguousLauncher(String),
 }
 
 // we must manually implement serde::Serialize
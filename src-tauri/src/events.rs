@@ -1,14 +1,26 @@
 pub mod system_listeners;
 pub mod types;
 
-use std::marker::PhantomData;
+use std::{
+    collections::HashMap,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Listener};
-use tracing::error;
+use tauri::{AppHandle, Emitter, EventId, Listener, Manager};
+use tokio::sync::mpsc;
+use tracing::{debug, error};
 
 use crate::error::OneClickLaunchError;
 
+use types::{ListenerFailed, ListenerFailedPayload};
+
 pub trait Event {
     type Payload: Serialize + for<'a> Deserialize<'a> + Clone;
 
@@ -27,21 +39,216 @@ impl<E: Event> EventDispatcher<E> {
     }
 }
 
+type ListenerFuture = Pin<Box<dyn Future<Output = Result<(), OneClickLaunchError>> + Send>>;
+
+type ListenerCallback = Arc<dyn Fn(AppHandle, &str) -> ListenerFuture + Send + Sync>;
+
+/// 异步监听器
+#[derive(Clone)]
+struct AsyncListener {
+    id: u64,
+    /// 监听器名称,出错时用于定位
+    name: &'static str,
+    callback: ListenerCallback,
+}
+
+/// 异步监听器注册表,需要作为全局状态注册到应用程序中
+///
+/// 每个事件对应一个队列,事件按发送顺序逐个处理,同一事件的监听器按注册顺序依次执行
+#[derive(Default)]
+pub struct ListenerRegistry {
+    queues: Mutex<HashMap<&'static str, EventQueue>>,
+    next_id: AtomicU64,
+}
+
+/// 事件队列
+struct EventQueue {
+    /// 向队列转发事件的监听id,取消后队列的处理任务随之结束
+    event_id: EventId,
+    listeners: Arc<RwLock<Vec<AsyncListener>>>,
+}
+
+/// 监听器句柄,用于取消监听
+pub struct ListenerHandle {
+    app: AppHandle,
+    kind: ListenerKind,
+}
+
+enum ListenerKind {
+    Sync(EventId),
+    Async { event: &'static str, id: u64 },
+}
+
+impl ListenerHandle {
+    /// 取消监听,正在执行的监听器不受影响
+    ///
+    /// 事件的最后一个异步监听器被取消时停止监听该事件,队列处理完已收到的事件后结束
+    pub fn unlisten(self) {
+        match self.kind {
+            ListenerKind::Sync(event_id) => self.app.unlisten(event_id),
+            ListenerKind::Async { event, id } => {
+                let registry = self.app.state::<ListenerRegistry>();
+                let mut queues = registry.queues.lock().unwrap();
+                let Some(queue) = queues.get(event) else {
+                    return;
+                };
+
+                let is_empty = {
+                    let mut listeners = queue.listeners.write().unwrap();
+                    listeners.retain(|l| l.id != id);
+                    listeners.is_empty()
+                };
+                if is_empty {
+                    if let Some(queue) = queues.remove(event) {
+                        self.app.unlisten(queue.event_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
 // 事件监听器注册系统
 pub struct EventSystem;
 
 impl EventSystem {
-    pub fn register_listener<E, F>(app: &AppHandle, _event: E, callback: F)
+    /// 注册同步监听器,回调在事件线程中直接执行
+    pub fn register_listener<E, F>(app: &AppHandle, _event: E, callback: F) -> ListenerHandle
     where
         E: Event + 'static,
         F: Fn(E::Payload) + Send + 'static,
     {
-        app.listen(E::name(), move |e| {
+        let event_id = app.listen(E::name(), move |e| {
             if let Ok(payload) = serde_json::from_str(e.payload()) {
                 callback(payload);
             } else {
-                error!("{}事件payload反序列化失败.原始数据: {}", "", e.payload());
+                error!(
+                    "{}事件payload反序列化失败.原始数据: {}",
+                    E::name(),
+                    e.payload()
+                );
             }
         });
+
+        ListenerHandle {
+            app: app.clone(),
+            kind: ListenerKind::Sync(event_id),
+        }
     }
+
+    /// 注册异步监听器
+    ///
+    /// 监听器返回的错误会记录到日志,并通过`ListenerFailed`事件通知
+    pub fn register_async_listener<E, F, Fut>(
+        app: &AppHandle,
+        _event: E,
+        name: &'static str,
+        callback: F,
+    ) -> ListenerHandle
+    where
+        E: Event + 'static,
+        E::Payload: Send,
+        F: Fn(AppHandle, E::Payload) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), OneClickLaunchError>> + Send + 'static,
+    {
+        let callback: ListenerCallback =
+            Arc::new(move |app: AppHandle, raw: &str| -> ListenerFuture {
+                match serde_json::from_str::<E::Payload>(raw) {
+                    Ok(payload) => Box::pin(callback(app, payload)),
+                    Err(e) => {
+                        let message = format!(
+                            "{}事件payload反序列化失败: {}.原始数据: {}",
+                            E::name(),
+                            e,
+                            raw
+                        );
+                        Box::pin(async move { Err(OneClickLaunchError::ExecutionError(message)) })
+                    }
+                }
+            });
+
+        let registry = app.state::<ListenerRegistry>();
+        let id = registry.next_id.fetch_add(1, Ordering::Relaxed);
+        let listener = AsyncListener { id, name, callback };
+
+        let mut queues = registry.queues.lock().unwrap();
+        match queues.get(E::name()) {
+            Some(queue) => queue.listeners.write().unwrap().push(listener),
+            None => {
+                let listeners = Arc::new(RwLock::new(vec![listener]));
+                let event_id = start_queue(app, E::name(), listeners.clone());
+                queues.insert(
+                    E::name(),
+                    EventQueue {
+                        event_id,
+                        listeners,
+                    },
+                );
+            }
+        }
+
+        ListenerHandle {
+            app: app.clone(),
+            kind: ListenerKind::Async {
+                event: E::name(),
+                id,
+            },
+        }
+    }
+}
+
+/// 监听事件并启动处理队列,返回监听id
+///
+/// 取消监听时发送端随回调一起释放,处理任务在接收完剩余事件后结束
+fn start_queue(
+    app: &AppHandle,
+    event: &'static str,
+    listeners: Arc<RwLock<Vec<AsyncListener>>>,
+) -> EventId {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+
+    let event_id = app.listen(event, move |e| {
+        let _ = sender.send(e.payload().to_string());
+    });
+
+    let app_cloned = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(payload) = receiver.recv().await {
+            // 复制当前的监听器列表,执行过程中取消或新增监听不影响本次事件
+            let current = listeners.read().unwrap().clone();
+            for listener in current {
+                debug!("{} 处理{}事件", listener.name, event);
+                if let Err(e) = (listener.callback)(app_cloned.clone(), &payload).await {
+                    report_failure(&app_cloned, event, listener.name, &e);
+                }
+            }
+        }
+        debug!("{}事件的处理队列已结束", event);
+    });
+
+    event_id
+}
+
+/// 记录监听器错误并发送`ListenerFailed`事件
+fn report_failure(
+    app: &AppHandle,
+    event: &'static str,
+    listener: &'static str,
+    e: &OneClickLaunchError,
+) {
+    error!("{} 处理{}事件失败.{:?}", listener, event, e);
+
+    // 错误事件自身的监听器失败时只记录日志,避免循环
+    if event == ListenerFailed::name() {
+        return;
+    }
+
+    let _ = EventDispatcher::<ListenerFailed>::send_event(
+        app,
+        ListenerFailedPayload {
+            event: event.to_string(),
+            listener: listener.to_string(),
+            message: e.to_string(),
+        },
+    );
 }
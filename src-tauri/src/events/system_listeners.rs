@@ -1,35 +1,43 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
 use tauri::{AppHandle, Manager};
 use tracing::{debug, error};
 
 use crate::{
     DatabaseManager,
     api::{
-        launcher_api,
-        setting_api::{self, check_launch_then_exit},
-        window_api,
+        backup_api, hook_api, launcher_api, setting_api::check_launch_then_exit, sync_api,
+        trash_api, window_api,
     },
+    cli::Cli,
     constants::{
-        self, AUTO_START_FLAG, AUTO_START_LAUNCHER_IDS_KEY, HIDE_AFTER_AUTO_START_KEY,
-        LAUNCH_SPECIFIED_LAUNCHER_KEY, THEME_KEY,
+        self, AUTO_START_LAUNCHER_IDS_KEY, HIDE_AFTER_AUTO_START_KEY, SYNC_INTERVAL_SECS_KEY,
+        THEME_KEY,
     },
-    db::{launcher_resource, settings},
+    error::OneClickLaunchError,
     events::EventDispatcher,
-    extract_arg_value,
+    setting_registry,
 };
 
 use super::{
-    EventSystem,
+    Event, EventSystem, ListenerHandle,
     types::{
         ApplicationStartupComplete, ApplicationStartupCompletePayload, LauncherBasicInfoUpdated,
-        LauncherLaunched, LauncherLaunchedPayload, SettingUpdated, SettingUpdatedPayload,
+        LauncherLaunched, LauncherLaunchedPayload, ProfileSwitched, ProfileSwitchedPayload,
+        ResourceCreated, ResourceDeleted, ResourceUpdated, SettingUpdated, SettingUpdatedPayload,
     },
 };
 
 pub fn register_system_listeners(app: &AppHandle) {
     register_launcher_launched_listeners(app);
     register_launcher_basic_info_updated_listeners(app);
+    register_resource_changed_listeners(app);
     register_application_startup_complete_listeners(app);
     register_setting_updated_listeners(app);
+    register_profile_switched_listeners(app);
 }
 
 /// 注册启动器启动事件监听器
@@ -38,32 +46,102 @@ fn register_launcher_launched_listeners(app: &AppHandle) {
     EventSystem::register_listener(app, LauncherLaunched, move |payload| {
         debug!("launcher_launched_listeners 处理中");
         hide_window(&app_handle, &payload);
-        launch_then_exit(&app_handle);
         debug!("launcher_launched_listeners 处理完成");
     });
+    // 钩子需要在启动后退出之前执行
+    EventSystem::register_async_listener(app, LauncherLaunched, "run_hooks", |app, payload| {
+        hook_api::run_hooks(app, LauncherLaunched::name(), payload)
+    });
+    EventSystem::register_async_listener(app, LauncherLaunched, "launch_then_exit", |app, _| {
+        launch_then_exit(app)
+    });
 }
 
 /// 注册启动器基础信息变更事件
 fn register_launcher_basic_info_updated_listeners(app: &AppHandle) {
-    let app_handle = app.clone();
-    EventSystem::register_listener(app, LauncherBasicInfoUpdated, move |_payload| {
-        debug!("launcher_basic_info_updated_listeners 处理中");
-        refresh_tray(&app_handle);
-        debug!("launcher_basic_info_updated_listeners 处理完成");
+    EventSystem::register_async_listener(
+        app,
+        LauncherBasicInfoUpdated,
+        "refresh_tray",
+        |app, _| window_api::refresh_tray(app),
+    );
+    EventSystem::register_async_listener(
+        app,
+        LauncherBasicInfoUpdated,
+        "export_sync_snapshot",
+        |app, _| export_sync_snapshot(app),
+    );
+}
+
+/// 注册资源变更事件监听器
+fn register_resource_changed_listeners(app: &AppHandle) {
+    EventSystem::register_async_listener(app, ResourceCreated, "export_sync_snapshot", |app, _| {
+        export_sync_snapshot(app)
+    });
+    EventSystem::register_async_listener(app, ResourceUpdated, "export_sync_snapshot", |app, _| {
+        export_sync_snapshot(app)
+    });
+    EventSystem::register_async_listener(app, ResourceDeleted, "export_sync_snapshot", |app, _| {
+        export_sync_snapshot(app)
     });
 }
 
 /// 注册应用程序启动完成监听器
+///
+/// 启动完成事件只发送一次,全部监听器执行完成后取消监听,释放事件队列
 fn register_application_startup_complete_listeners(app: &AppHandle) {
     let app_cloned = app.clone();
-    EventSystem::register_listener(app, ApplicationStartupComplete, move |payload| {
-        debug!("application_startup_complete_listeners 处理中");
-        hide_after_auto_start(&app_cloned, &payload);
-        refresh_tray(&app_cloned);
-        launch_auto_start_launchers(&app_cloned, &payload);
-        launch_specified_launcher(&app_cloned, &payload);
-        debug!("application_startup_complete_listeners 处理完成");
-    });
+    let handles = vec![
+        EventSystem::register_listener(app, ApplicationStartupComplete, move |payload| {
+            debug!("application_startup_complete_listeners 处理中");
+            hide_after_auto_start(&app_cloned, &payload);
+            purge_expired_trash(&app_cloned);
+            backup_database_periodically(&app_cloned);
+            sync_periodically(&app_cloned);
+            debug!("application_startup_complete_listeners 处理完成");
+        }),
+        EventSystem::register_async_listener(
+            app,
+            ApplicationStartupComplete,
+            "refresh_tray",
+            |app, _| window_api::refresh_tray(app),
+        ),
+        EventSystem::register_async_listener(
+            app,
+            ApplicationStartupComplete,
+            "launch_auto_start_launchers",
+            launch_auto_start_launchers,
+        ),
+        EventSystem::register_async_listener(
+            app,
+            ApplicationStartupComplete,
+            "launch_specified_launcher",
+            launch_specified_launcher,
+        ),
+        // 钩子可能执行较长时间,放在最后避免推迟托盘刷新与自启启动器
+        EventSystem::register_async_listener(
+            app,
+            ApplicationStartupComplete,
+            "run_hooks",
+            |app, payload| hook_api::run_hooks(app, ApplicationStartupComplete::name(), payload),
+        ),
+    ];
+
+    let handles = Arc::new(Mutex::new(handles));
+    let handles_cloned = handles.clone();
+    let handle = EventSystem::register_async_listener(
+        app,
+        ApplicationStartupComplete,
+        "unlisten_startup_complete",
+        move |_, _| {
+            let handles = std::mem::take(&mut *handles_cloned.lock().unwrap());
+            async move {
+                handles.into_iter().for_each(ListenerHandle::unlisten);
+                Ok(())
+            }
+        },
+    );
+    handles.lock().unwrap().push(handle);
 }
 
 /// 注册应用程序设置修改监听器
@@ -74,23 +152,104 @@ fn register_setting_updated_listeners(app: &AppHandle) {
         change_theme(&app_cloned, &payload);
         debug!("setting_updated_listeners 处理完成");
     });
+    EventSystem::register_async_listener(app, SettingUpdated, "run_hooks", |app, payload| {
+        hook_api::run_hooks(app, SettingUpdated::name(), payload)
+    });
 }
 
-/// 刷新系统图标
-fn refresh_tray(app: &AppHandle) {
+/// 注册切换配置完成监听器
+fn register_profile_switched_listeners(app: &AppHandle) {
+    let app_cloned = app.clone();
+    EventSystem::register_listener(app, ProfileSwitched, move |payload| {
+        debug!("profile_switched_listeners 处理中");
+        reload_main_window(&app_cloned, &payload);
+        debug!("profile_switched_listeners 处理完成");
+    });
+    EventSystem::register_async_listener(app, ProfileSwitched, "refresh_tray", |app, _| {
+        window_api::refresh_tray(app)
+    });
+}
+
+/// 定期清理回收站中超过保留天数的数据
+fn purge_expired_trash(app: &AppHandle) {
     let app_cloned = app.clone();
     tauri::async_runtime::spawn(async move {
-        if let Err(e) = window_api::refresh_tray(app_cloned).await {
-            error!("刷新系统图标失败.{:?}", e);
-        } else {
-            debug!("刷新系统图标成功.");
+        loop {
+            let pool = app_cloned.state::<DatabaseManager>().pool();
+            if let Err(e) = trash_api::purge_expired_trash(&pool).await {
+                error!("清理回收站失败.{:?}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(6 * 60 * 60)).await;
         }
     });
 }
 
+/// 定期检查是否需要执行定时备份
+fn backup_database_periodically(app: &AppHandle) {
+    let app_cloned = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // 每次循环只读取一次,避免期间切换配置导致连接池与数据库文件不一致
+            let (pool, db_path) = app_cloned.state::<DatabaseManager>().pool_and_path();
+            if let Err(e) = backup_api::run_scheduled_backup(&pool, &db_path).await {
+                error!("定时备份数据库失败.{:?}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        }
+    });
+}
+
+/// 切换配置后重新加载主窗口,使页面读取新配置的数据
+fn reload_main_window(app: &AppHandle, payload: &ProfileSwitchedPayload) {
+    if let Some(window) = app.get_webview_window(constants::MAIN_WINDOW_LABEL) {
+        match window.eval("window.location.reload()") {
+            Ok(_) => debug!("已重新加载主窗口, 当前配置: {}", payload.profile_id),
+            Err(e) => error!("重新加载主窗口失败.{:?}", e),
+        }
+    }
+}
+
+/// 启动器或资源变更后写出本机的同步文件
+async fn export_sync_snapshot(app: AppHandle) -> Result<(), OneClickLaunchError> {
+    let pool = app.state::<DatabaseManager>().pool();
+    sync_api::export_snapshot(&pool).await?;
+    Ok(())
+}
+
+/// 定期检查同步文件夹中其他设备的变更
+fn sync_periodically(app: &AppHandle) {
+    let app_cloned = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // 每次循环只读取一次连接池,期间切换配置时本次同步仍使用同一个数据库
+            let pool = app_cloned.state::<DatabaseManager>().pool();
+            if let Err(e) = sync_api::run_sync(&app_cloned, &pool).await {
+                error!("同步启动器失败.{:?}", e);
+            }
+
+            let interval_secs =
+                match setting_registry::read_integer(&pool, SYNC_INTERVAL_SECS_KEY).await {
+                    Ok(secs) => secs,
+                    Err(e) => {
+                        // 读取失败时使用默认间隔,避免频繁扫描同步文件夹
+                        error!("读取同步间隔失败.{:?}", e);
+                        setting_registry::default_integer(SYNC_INTERVAL_SECS_KEY)
+                    }
+                }
+                .max(1) as u64;
+            tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+        }
+    });
+}
+
+/// 是否为操作系统触发的开机自启,此时命令行参数包含`--auto`
+fn is_auto_start(payload: &ApplicationStartupCompletePayload) -> bool {
+    Cli::from_argv(&payload.args).is_some_and(|cli| cli.auto)
+}
+
 /// 应用程序自动启动后隐藏
 fn hide_after_auto_start(app: &AppHandle, payload: &ApplicationStartupCompletePayload) {
-    if payload.args.contains(&AUTO_START_FLAG) {
+    if is_auto_start(payload) {
         debug!(
             "hide_after_auto_start 判断为自动启动, 命令行参数: {:?}",
             payload.args
@@ -107,12 +266,8 @@ fn hide_after_auto_start(app: &AppHandle, payload: &ApplicationStartupCompletePa
                 .get_webview_window(constants::MAIN_WINDOW_LABEL)
                 .unwrap();
 
-            match settings::read(&db_manager.pool, HIDE_AFTER_AUTO_START_KEY)
-                .await
-                .ok()
-                .flatten()
-            {
-                Some(settings) if setting_api::string_to_bool(&settings.value) => {
+            match setting_registry::read_bool(&db_manager.pool(), HIDE_AFTER_AUTO_START_KEY).await {
+                Ok(true) => {
                     let _ = window.hide();
                 }
                 _ => {
@@ -128,90 +283,85 @@ fn hide_after_auto_start(app: &AppHandle, payload: &ApplicationStartupCompletePa
     }
 }
 
-fn launch_auto_start_launchers(app: &AppHandle, payload: &ApplicationStartupCompletePayload) {
+/// 操作系统以开机自启启动应用程序时,启动设置的自启启动器
+async fn launch_auto_start_launchers(
+    app: AppHandle,
+    payload: ApplicationStartupCompletePayload,
+) -> Result<(), OneClickLaunchError> {
     // 检查启动参数, 当命令包含`--auto`时表示是操作系统触发的自动启动
-    if payload.args.contains(&AUTO_START_FLAG) {
+    if !is_auto_start(&payload) {
         debug!(
-            "launch_auto_start_launchers 判断为自动启动, 命令行参数: {:?}",
+            "launch_auto_start_launchers 判断为非自动启动, 命令行参数: {:?}",
             payload.args
         );
+        return Ok(());
+    }
 
-        let inner_app = app.clone();
+    debug!(
+        "launch_auto_start_launchers 判断为自动启动, 命令行参数: {:?}",
+        payload.args
+    );
 
-        tauri::async_runtime::spawn(async move {
-            let app_cloned = inner_app.clone();
+    let pool = app.state::<DatabaseManager>().pool();
 
-            let db_manager = app_cloned.state::<DatabaseManager>();
+    let auto_start_launcher_ids =
+        setting_registry::read_id_list(&pool, AUTO_START_LAUNCHER_IDS_KEY).await?;
+    if auto_start_launcher_ids.is_empty() {
+        debug!("launch_auto_start_launchers 没有找到自启动启动器");
+        return Ok(());
+    }
 
-            if let Ok(Some(settings)) =
-                settings::read(&db_manager.pool, AUTO_START_LAUNCHER_IDS_KEY).await
-            {
-                if let Ok(auto_start_launcher_ids) =
-                    serde_json::from_str::<Vec<i64>>(&settings.value)
-                {
-                    if let Ok(launcher_resources) = launcher_resource::query_by_launcher_ids(
-                        &db_manager.pool,
-                        &auto_start_launcher_ids,
-                    )
-                    .await
-                    {
-                        // 如果用户设置的自启启动器为空则不执行启动
-                        if !launcher_resources.is_empty() {
-                            launcher_api::launch_resources(&app_cloned, &launcher_resources);
-                            debug!("自启启动器已启动. 启动器信息: {:?}", launcher_resources);
-                            let _ = EventDispatcher::<LauncherLaunched>::send_event(
-                                &app_cloned,
-                                LauncherLaunchedPayload {
-                                    launcher_ids: auto_start_launcher_ids.clone(),
-                                },
-                            );
-                        } else {
-                            debug!("开启了自启启动器,但启动器为空");
-                        }
-                    }
-                } else {
-                    debug!(
-                        "launch_auto_start_launchers 自启动启动器反序列化失败,原始数据: {}",
-                        &settings.value
-                    );
-                }
-            } else {
-                debug!("launch_auto_start_launchers 没有找到自启动启动器");
+    // 与手动启动一样依次执行启动前命令、资源与启动后命令
+    let mut launched_ids = vec![];
+    for launcher_id in auto_start_launcher_ids {
+        match launcher_api::launch_launcher(&app, launcher_id).await {
+            Ok(report) if !report.aborted && !report.resources.is_empty() => {
+                launched_ids.push(launcher_id)
             }
-        });
-    } else {
-        debug!(
-            "launch_auto_start_launchers 判断为非自动启动, 命令行参数: {:?}",
-            payload.args
-        );
+            Ok(report) => debug!("自启启动器 {} 未启动: {:?}", launcher_id, report),
+            Err(e) => error!("自启启动器 {} 启动失败.{:?}", launcher_id, e),
+        }
     }
-}
 
-fn launch_specified_launcher(app: &AppHandle, payload: &ApplicationStartupCompletePayload) {
-    // 如果启动命令指定了LAUNCH_SPECIFIED_LAUNCHER_KEY, 需要启动指定的编组
-    if let Some(Ok(launcher_id)) = extract_arg_value(&payload.args, &LAUNCH_SPECIFIED_LAUNCHER_KEY)
-        .map(|value| value.parse::<i64>())
-    {
-        let app_cloned = app.clone();
-        tokio::spawn(async move {
-            if let Err(e) = launcher_api::launch(app_cloned, launcher_id).await {
-                tracing::error!("launcher launch fail: {}", e);
-            }
-        });
+    // 如果用户设置的自启启动器为空则不发送启动事件
+    if launched_ids.is_empty() {
+        debug!("开启了自启启动器,但没有启动任何资源");
+        return Ok(());
     }
+
+    debug!("自启启动器已启动. 启动器id: {:?}", launched_ids);
+    EventDispatcher::<LauncherLaunched>::send_event(
+        &app,
+        LauncherLaunchedPayload {
+            launcher_ids: launched_ids,
+        },
+    )?;
+
+    Ok(())
 }
 
-fn launch_then_exit(app: &AppHandle) {
-    let app_cloned = app.clone();
-    tauri::async_runtime::spawn(async move {
-        let db = app_cloned.state::<DatabaseManager>();
-        if let Ok(_exit @ true) = check_launch_then_exit(&db.pool).await {
-            debug!("launch_then_exit 已设置启动后退出, 正在退出程序.");
-            app_cloned.exit(0);
-        } else {
-            debug!("launch_then_exit 未设置启动后退出");
+/// 启动命令为`launch <启动器>`时, 启动指定的编组
+async fn launch_specified_launcher(
+    app: AppHandle,
+    payload: ApplicationStartupCompletePayload,
+) -> Result<(), OneClickLaunchError> {
+    if let Some(cli) = Cli::from_argv(&payload.args) {
+        if let Some(launcher) = cli.launcher_to_launch() {
+            launcher_api::launch_by_query(app, launcher).await?;
         }
-    });
+    }
+    Ok(())
+}
+
+async fn launch_then_exit(app: AppHandle) -> Result<(), OneClickLaunchError> {
+    let pool = app.state::<DatabaseManager>().pool();
+    if check_launch_then_exit(&pool).await? {
+        debug!("launch_then_exit 已设置启动后退出, 正在退出程序.");
+        app.exit(0);
+    } else {
+        debug!("launch_then_exit 未设置启动后退出");
+    }
+    Ok(())
 }
 
 /// 隐藏窗口: 启动器启动隐藏窗口
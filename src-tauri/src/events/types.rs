@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::launcher_api::LauncherResourceVo;
+
 use super::Event;
 
 /// 启动器被启动的事件
@@ -94,3 +96,97 @@ impl Event for DragDropResource {
         "launcher:drag_drop_resource"
     }
 }
+
+/// 切换配置完成
+pub struct ProfileSwitched;
+
+/// 切换配置完成载荷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileSwitchedPayload {
+    /// 切换后的配置id
+    pub profile_id: String,
+}
+
+impl Event for ProfileSwitched {
+    type Payload = ProfileSwitchedPayload;
+
+    fn name() -> &'static str {
+        "profile:switched"
+    }
+}
+
+/// 资源被添加
+pub struct ResourceCreated;
+
+/// 资源被添加载荷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceCreatedPayload {
+    /// 新增的资源
+    pub resources: Vec<LauncherResourceVo>,
+}
+
+impl Event for ResourceCreated {
+    type Payload = ResourceCreatedPayload;
+
+    fn name() -> &'static str {
+        "resource:created"
+    }
+}
+
+/// 资源被修改
+pub struct ResourceUpdated;
+
+/// 资源被修改载荷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceUpdatedPayload {
+    /// 修改后的资源
+    pub resources: Vec<LauncherResourceVo>,
+}
+
+impl Event for ResourceUpdated {
+    type Payload = ResourceUpdatedPayload;
+
+    fn name() -> &'static str {
+        "resource:updated"
+    }
+}
+
+/// 资源被删除
+pub struct ResourceDeleted;
+
+/// 资源被删除载荷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceDeletedPayload {
+    /// 删除前的资源
+    pub resources: Vec<LauncherResourceVo>,
+}
+
+impl Event for ResourceDeleted {
+    type Payload = ResourceDeletedPayload;
+
+    fn name() -> &'static str {
+        "resource:deleted"
+    }
+}
+
+/// 异步监听器执行失败
+pub struct ListenerFailed;
+
+/// 异步监听器执行失败载荷
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ListenerFailedPayload {
+    /// 事件名称
+    pub event: String,
+    /// 监听器名称
+    pub listener: String,
+    /// 错误信息
+    pub message: String,
+}
+
+impl Event for ListenerFailed {
+    type Payload = ListenerFailedPayload;
+
+    fn name() -> &'static str {
+        "event:listener_failed"
+    }
+}
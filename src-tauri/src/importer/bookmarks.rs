@@ -0,0 +1,246 @@
+use std::{fs, path::Path};
+
+use crate::error::OneClickLaunchError;
+
+use super::markup::{self, Token};
+
+/// 书签文件中的一个文件夹,只包含直接位于该文件夹下的书签
+#[derive(Debug, Clone)]
+pub struct BookmarkFolder {
+    /// 按文件中出现顺序(先序)分配的编号,同一文件多次解析结果一致
+    pub id: usize,
+    pub parent_id: Option<usize>,
+    pub name: String,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// 书签
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+}
+
+/// 读取并解析浏览器导出的Netscape书签HTML文件
+pub fn parse_file(path: &Path) -> Result<Vec<BookmarkFolder>, OneClickLaunchError> {
+    let content = fs::read_to_string(path)?;
+    parse(&content)
+}
+
+/// 解析Netscape书签HTML,返回先序排列的文件夹列表,第一个文件夹为书签根目录
+///
+/// 文件结构为`<DT><H3>文件夹</H3><DL>...</DL>`与`<DT><A HREF="...">标题</A>`的嵌套
+pub fn parse(content: &str) -> Result<Vec<BookmarkFolder>, OneClickLaunchError> {
+    let tokens = markup::tokenize(content);
+
+    if !tokens
+        .iter()
+        .any(|t| matches!(t, Token::Start { name, .. } if name == "dl"))
+    {
+        return Err(OneClickLaunchError::ExecutionError(
+            "不是有效的书签文件".to_string(),
+        ));
+    }
+
+    let mut folders: Vec<BookmarkFolder> = vec![];
+    let mut stack: Vec<usize> = vec![];
+    let mut root_name = "书签".to_string();
+    let mut pending_folder_name: Option<String> = None;
+    let mut iter = tokens.iter().peekable();
+
+    while let Some(token) = iter.next() {
+        match token {
+            Token::Start { name, .. } if name == "h1" => {
+                if let Some(text) = read_text(&mut iter, "h1") {
+                    root_name = text;
+                }
+            }
+            Token::Start { name, .. } if name == "h3" => {
+                pending_folder_name = read_text(&mut iter, "h3");
+            }
+            Token::Start { name, .. } if name == "dl" => {
+                let id = folders.len();
+                let folder_name = if stack.is_empty() {
+                    root_name.clone()
+                } else {
+                    pending_folder_name.take().unwrap_or_default()
+                };
+                folders.push(BookmarkFolder {
+                    id,
+                    parent_id: stack.last().copied(),
+                    name: folder_name,
+                    bookmarks: vec![],
+                });
+                stack.push(id);
+            }
+            Token::End { name } if name == "dl" => {
+                stack.pop();
+            }
+            Token::Start { name, .. } if name == "a" => {
+                let url = token
+                    .attribute("href")
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                let title = read_text(&mut iter, "a").unwrap_or_default();
+                if let Some(folder) = stack.last().and_then(|id| folders.get_mut(*id)) {
+                    if is_launchable_url(&url) {
+                        folder.bookmarks.push(Bookmark { title, url });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(folders)
+}
+
+/// 计算从根目录到指定文件夹的名称路径,不包含根目录
+pub fn name_path(folders: &[BookmarkFolder], folder_id: usize) -> Vec<String> {
+    let mut path = vec![];
+    let mut current = folders.get(folder_id);
+    while let Some(folder) = current {
+        if folder.parent_id.is_none() {
+            break;
+        }
+        path.push(folder.name.clone());
+        current = folder.parent_id.and_then(|id| folders.get(id));
+    }
+    path.reverse();
+    path
+}
+
+/// 读取标签内的文本,直到遇到对应的结束标签
+fn read_text<'a, I>(iter: &mut std::iter::Peekable<I>, tag: &str) -> Option<String>
+where
+    I: Iterator<Item = &'a Token>,
+{
+    let mut text = String::new();
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Text(t) => text.push_str(t),
+            Token::End { name } if name == tag => {
+                iter.next();
+                break;
+            }
+            // 遇到其他标签说明文件格式不规范,停止读取
+            Token::Start { .. } | Token::End { .. } => break,
+        }
+        iter.next();
+    }
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// 浏览器内部地址(如Firefox的`place:`)与脚本书签无法作为资源打开
+fn is_launchable_url(url: &str) -> bool {
+    !url.is_empty() && !url.starts_with("place:") && !url.starts_with("javascript:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREFOX: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file. -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<meta http-equiv="Content-Security-Policy" content="default-src 'self'; script-src 'none'; img-src data: *; object-src 'none'"></meta>
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks Menu</H1>
+
+<DL><p>
+    <DT><A HREF="place:parent=menu________&sort=12" ADD_DATE="1700000000">Recent Tags</A>
+    <DT><H3 ADD_DATE="1700000000" LAST_MODIFIED="1700000001">Mozilla Firefox</H3>
+    <DL><p>
+        <DT><A HREF="https://support.mozilla.org/" ADD_DATE="1700000000">Get Help</A>
+        <DT><A HREF="javascript:alert(1)">Bookmarklet</A>
+    </DL><p>
+    <DT><H3 ADD_DATE="1700000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks Toolbar</H3>
+    <DL><p>
+        <DT><H3>Work</H3>
+        <DL><p>
+            <DT><A HREF="https://example.com/?a=1&amp;b=2">Example &amp; Co</A>
+        </DL><p>
+    </DL><p>
+</DL>
+"#;
+
+    const CHROME: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000" LAST_MODIFIED="0" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://www.rust-lang.org/" ADD_DATE="1700000000" ICON="data:image/png;base64,AAAA">Rust</A>
+    </DL><p>
+    <DT><A HREF="https://docs.rs/" ADD_DATE="1700000000">Docs.rs</A>
+</DL><p>
+"#;
+
+    #[test]
+    fn parse_firefox_export() {
+        let folders = parse(FIREFOX).unwrap();
+
+        let names: Vec<&str> = folders.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Bookmarks Menu",
+                "Mozilla Firefox",
+                "Bookmarks Toolbar",
+                "Work"
+            ]
+        );
+        assert_eq!(folders[0].parent_id, None);
+        assert_eq!(folders[3].parent_id, Some(2));
+
+        // place:与javascript:书签被跳过
+        assert!(folders[0].bookmarks.is_empty());
+        assert_eq!(folders[1].bookmarks.len(), 1);
+        assert_eq!(folders[1].bookmarks[0].title, "Get Help");
+        assert_eq!(folders[1].bookmarks[0].url, "https://support.mozilla.org/");
+
+        assert_eq!(folders[3].bookmarks[0].title, "Example & Co");
+        assert_eq!(folders[3].bookmarks[0].url, "https://example.com/?a=1&b=2");
+    }
+
+    #[test]
+    fn parse_chrome_export() {
+        let folders = parse(CHROME).unwrap();
+
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].name, "Bookmarks");
+        assert_eq!(folders[0].bookmarks[0].url, "https://docs.rs/");
+        assert_eq!(folders[1].name, "Bookmarks bar");
+        assert_eq!(folders[1].parent_id, Some(0));
+        assert_eq!(folders[1].bookmarks[0].title, "Rust");
+    }
+
+    #[test]
+    fn parse_rejects_non_bookmark_file() {
+        assert!(parse("<html><body>hello</body></html>").is_err());
+    }
+
+    #[test]
+    fn name_path_excludes_root() {
+        let folders = parse(FIREFOX).unwrap();
+
+        assert!(name_path(&folders, 0).is_empty());
+        assert_eq!(name_path(&folders, 1), vec!["Mozilla Firefox"]);
+        assert_eq!(name_path(&folders, 3), vec!["Bookmarks Toolbar", "Work"]);
+        assert!(name_path(&folders, 99).is_empty());
+    }
+
+    #[test]
+    fn launchable_urls() {
+        assert!(is_launchable_url("https://example.com"));
+        assert!(!is_launchable_url(""));
+        assert!(!is_launchable_url("place:sort=8"));
+        assert!(!is_launchable_url("javascript:void(0)"));
+    }
+}
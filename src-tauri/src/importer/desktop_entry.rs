@@ -0,0 +1,371 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::OneClickLaunchError;
+
+/// freedesktop桌面入口文件的扩展名
+pub const DESKTOP_ENTRY_EXTENSION: &str = "desktop";
+
+/// 未找到系统默认终端时依次尝试的终端程序及其执行命令参数
+const TERMINAL_CANDIDATES: [(&str, &str); 4] = [
+    ("x-terminal-emulator", "-e"),
+    ("gnome-terminal", "--"),
+    ("konsole", "-e"),
+    ("xterm", "-e"),
+];
+
+/// 解析后的freedesktop桌面入口(.desktop文件)
+#[derive(Debug, Clone, Default)]
+pub struct DesktopEntry {
+    /// 入口名称(未本地化的Name)
+    pub name: String,
+    /// 去除字段代码后的Exec命令
+    pub exec: Vec<String>,
+    /// 工作目录(Path)
+    pub working_dir: Option<String>,
+    /// 图标名称或路径(Icon)
+    pub icon: Option<String>,
+    /// 是否需要在终端中运行(Terminal)
+    pub terminal: bool,
+    /// Type=Link时的链接地址(URL)
+    pub url: Option<String>,
+    /// 是否为不应展示的入口(NoDisplay/Hidden)
+    pub hidden: bool,
+}
+
+impl DesktopEntry {
+    /// 读取并解析桌面入口文件
+    pub fn from_file(path: &Path) -> Result<Self, OneClickLaunchError> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    /// 解析桌面入口文件内容,只读取`[Desktop Entry]`分组
+    pub fn parse(content: &str) -> Result<Self, OneClickLaunchError> {
+        let mut entry = DesktopEntry::default();
+        let mut in_main_group = false;
+        let mut entry_type = None;
+        let mut exec = None;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                in_main_group = line == "[Desktop Entry]";
+                continue;
+            }
+            if !in_main_group {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            // 带有语言后缀的本地化键(如Name[zh_CN])会因为键名不一致而被忽略
+            let value = unescape_value(value.trim());
+            match key.trim() {
+                "Type" => entry_type = Some(value),
+                "Name" => entry.name = value,
+                "Exec" => exec = Some(value),
+                "Path" if !value.is_empty() => entry.working_dir = Some(value),
+                "Icon" if !value.is_empty() => entry.icon = Some(value),
+                "URL" if !value.is_empty() => entry.url = Some(value),
+                "Terminal" => entry.terminal = value == "true",
+                "NoDisplay" | "Hidden" => entry.hidden |= value == "true",
+                _ => {}
+            }
+        }
+
+        if entry.name.is_empty() {
+            return Err(OneClickLaunchError::ExecutionError(
+                "桌面入口文件缺少Name".to_string(),
+            ));
+        }
+
+        match entry_type.as_deref() {
+            Some("Link") if entry.url.is_some() => {}
+            Some("Application") | None => {
+                let exec = exec.ok_or_else(|| {
+                    OneClickLaunchError::ExecutionError("桌面入口文件缺少Exec".to_string())
+                })?;
+                entry.exec = parse_exec(&exec)?;
+            }
+            Some(other) => {
+                return Err(OneClickLaunchError::ExecutionError(format!(
+                    "不支持的桌面入口类型: {}",
+                    other
+                )));
+            }
+        }
+
+        Ok(entry)
+    }
+
+    /// 转换为可以直接执行的资源路径
+    ///
+    /// 程序名会在PATH中查找为绝对路径;需要终端时使用系统终端包裹命令,
+    /// 指定了工作目录时通过`sh -c`切换目录后再执行
+    pub fn to_resource_path(&self) -> Result<String, OneClickLaunchError> {
+        if let Some(url) = &self.url {
+            return Ok(url.clone());
+        }
+
+        let mut argv = self.exec.clone();
+        if let Some(program) = argv.first_mut() {
+            if let Some(resolved) = find_in_path(program) {
+                *program = resolved.to_string_lossy().to_string();
+            }
+        }
+
+        if let Some(dir) = &self.working_dir {
+            let mut wrapped = vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"cd "$0" && exec "$@""#.to_string(),
+                dir.clone(),
+            ];
+            wrapped.extend(argv);
+            argv = wrapped;
+        }
+
+        if self.terminal {
+            let (terminal, exec_flag) = TERMINAL_CANDIDATES
+                .iter()
+                .find_map(|(name, flag)| find_in_path(name).map(|path| (path, *flag)))
+                .ok_or_else(|| {
+                    OneClickLaunchError::ExecutionError("未找到可用的终端程序".to_string())
+                })?;
+            let mut wrapped = vec![
+                terminal.to_string_lossy().to_string(),
+                exec_flag.to_string(),
+            ];
+            wrapped.extend(argv);
+            argv = wrapped;
+        }
+
+        shlex::try_join(argv.iter().map(String::as_str))
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))
+    }
+}
+
+/// 判断路径是否为桌面入口文件
+pub fn is_desktop_entry(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(DESKTOP_ENTRY_EXTENSION))
+}
+
+/// 用户级应用程序入口目录: $XDG_DATA_HOME/applications,默认为~/.local/share/applications
+pub fn user_applications_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("applications"))
+}
+
+/// 扫描目录中的桌面入口文件,无法解析的文件会被忽略
+pub fn scan_dir(dir: &Path) -> Result<Vec<(PathBuf, DesktopEntry)>, OneClickLaunchError> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut entries = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| is_desktop_entry(&p.to_string_lossy()))
+        .filter_map(|p| match DesktopEntry::from_file(&p) {
+            Ok(entry) => Some((p, entry)),
+            Err(e) => {
+                tracing::debug!("解析桌面入口文件失败: {:?}, {:?}", p, e);
+                None
+            }
+        })
+        .collect::<Vec<(PathBuf, DesktopEntry)>>();
+
+    entries.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+
+    Ok(entries)
+}
+
+/// 处理字符串类型值的转义序列
+fn unescape_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                // 其余转义属于Exec的引号规则,保留给命令解析处理
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 拆分Exec命令并去除字段代码(%f、%U等),`%%`还原为`%`
+fn parse_exec(exec: &str) -> Result<Vec<String>, OneClickLaunchError> {
+    let args = shlex::split(exec)
+        .ok_or_else(|| OneClickLaunchError::ExecutionError(format!("无法解析Exec: {}", exec)))?;
+
+    let argv = args
+        .iter()
+        .filter_map(|arg| {
+            let stripped = strip_field_codes(arg);
+            // 仅由字段代码构成的参数整体移除
+            (!stripped.is_empty() || arg.is_empty()).then_some(stripped)
+        })
+        .collect::<Vec<String>>();
+
+    if argv.is_empty() {
+        return Err(OneClickLaunchError::ExecutionError(format!(
+            "Exec中没有可执行的程序: {}",
+            exec
+        )));
+    }
+
+    Ok(argv)
+}
+
+fn strip_field_codes(arg: &str) -> String {
+    let mut result = String::with_capacity(arg.len());
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => result.push('%'),
+            // 其余字段代码(包括已废弃的)全部丢弃
+            Some(_) | None => {}
+        }
+    }
+    result
+}
+
+/// 在PATH环境变量中查找程序,已经是路径的程序直接判断是否存在
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.exists().then(|| path.to_path_buf());
+    }
+
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .map(|dir| dir.join(program))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_application() {
+        let entry = DesktopEntry::parse(
+            "# comment\n\
+             [Desktop Entry]\n\
+             Type=Application\n\
+             Name=Text Editor\n\
+             Name[zh_CN]=文本编辑器\n\
+             Exec=gedit %U\n\
+             Icon=org.gnome.gedit\n\
+             Path=/home/user/docs\n\
+             Terminal=false\n\
+             \n\
+             [Desktop Action new-window]\n\
+             Name=New Window\n\
+             Exec=gedit --new-window\n",
+        )
+        .unwrap();
+
+        assert_eq!(entry.name, "Text Editor");
+        assert_eq!(entry.exec, vec!["gedit"]);
+        assert_eq!(entry.icon.as_deref(), Some("org.gnome.gedit"));
+        assert_eq!(entry.working_dir.as_deref(), Some("/home/user/docs"));
+        assert!(!entry.terminal);
+        assert!(!entry.hidden);
+    }
+
+    #[test]
+    fn parse_link() {
+        let entry = DesktopEntry::parse(
+            "[Desktop Entry]\nType=Link\nName=Rust\nURL=https://www.rust-lang.org/\n",
+        )
+        .unwrap();
+
+        assert_eq!(entry.url.as_deref(), Some("https://www.rust-lang.org/"));
+        assert!(entry.exec.is_empty());
+        assert_eq!(
+            entry.to_resource_path().unwrap(),
+            "https://www.rust-lang.org/"
+        );
+    }
+
+    #[test]
+    fn parse_no_display() {
+        let entry =
+            DesktopEntry::parse("[Desktop Entry]\nName=Helper\nExec=helper\nNoDisplay=true\n")
+                .unwrap();
+        assert!(entry.hidden);
+
+        let entry = DesktopEntry::parse("[Desktop Entry]\nName=Helper\nExec=helper\nHidden=true\n")
+            .unwrap();
+        assert!(entry.hidden);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_entries() {
+        assert!(DesktopEntry::parse("[Desktop Entry]\nExec=foo\n").is_err());
+        assert!(DesktopEntry::parse("[Desktop Entry]\nName=Foo\n").is_err());
+        assert!(DesktopEntry::parse("[Desktop Entry]\nType=Directory\nName=Foo\n").is_err());
+        assert!(DesktopEntry::parse("[Desktop Entry]\nType=Link\nName=Foo\n").is_err());
+    }
+
+    #[test]
+    fn parse_exec_quoted_arguments() {
+        assert_eq!(
+            parse_exec(r#""/opt/My App/app" --title "a \"b\"" %f"#).unwrap(),
+            vec!["/opt/My App/app", "--title", r#"a "b""#]
+        );
+        // 转义后的值中\s已还原为空格
+        let entry = DesktopEntry::parse("[Desktop Entry]\nName=A\nExec=app\\s--flag %u\n").unwrap();
+        assert_eq!(entry.exec, vec!["app", "--flag"]);
+    }
+
+    #[test]
+    fn parse_exec_field_codes() {
+        assert_eq!(
+            parse_exec("app --percent 100%% --file=%f %U %i %c %k").unwrap(),
+            vec!["app", "--percent", "100%", "--file="]
+        );
+        assert!(parse_exec("%U").is_err());
+    }
+
+    #[test]
+    fn strip_field_codes_keeps_literal_percent() {
+        assert_eq!(strip_field_codes("%U"), "");
+        assert_eq!(strip_field_codes("%%"), "%");
+        assert_eq!(strip_field_codes("50%%off%f"), "50%off");
+        assert_eq!(strip_field_codes("trailing%"), "trailing");
+    }
+
+    #[test]
+    fn desktop_entry_extension() {
+        assert!(is_desktop_entry("/usr/share/applications/gedit.desktop"));
+        assert!(is_desktop_entry("Foo.DESKTOP"));
+        assert!(!is_desktop_entry("/usr/bin/gedit"));
+    }
+}
@@ -0,0 +1,260 @@
+/// 标记中的一个片段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// 开始标签,标签名统一为小写
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    /// 结束标签,标签名统一为小写
+    End { name: String },
+    /// 标签之间的文本,已解码字符实体
+    Text(String),
+}
+
+impl Token {
+    /// 按名称(不区分大小写)读取开始标签的属性
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Token::Start { attributes, .. } => attributes
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// 把标记文本拆分为标签与文本片段,注释、声明与处理指令会被忽略
+pub fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut tokens, rest);
+            break;
+        };
+
+        push_text(&mut tokens, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map(|end| &after[end + 3..]).unwrap_or("");
+            continue;
+        }
+
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").unwrap_or(after.len());
+            if !after[..end].is_empty() {
+                tokens.push(Token::Text(after[..end].to_string()));
+            }
+            rest = after.get(end + 3..).unwrap_or("");
+            continue;
+        }
+
+        let Some(end) = find_tag_end(rest) else {
+            // 未闭合的标签按文本处理
+            push_text(&mut tokens, rest);
+            break;
+        };
+
+        let body = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if body.starts_with('!') || body.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = body.strip_prefix('/') {
+            tokens.push(Token::End {
+                name: name.trim().to_lowercase(),
+            });
+            continue;
+        }
+
+        let self_closing = body.ends_with('/');
+        let body = body.trim_end_matches('/');
+        let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+
+        tokens.push(Token::Start {
+            name: body[..name_end].to_lowercase(),
+            attributes: parse_attributes(&body[name_end..]),
+            self_closing,
+        });
+    }
+
+    tokens
+}
+
+/// 解码字符实体
+pub fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn push_text(tokens: &mut Vec<Token>, text: &str) {
+    if !text.trim().is_empty() {
+        tokens.push(Token::Text(decode_entities(text)));
+    }
+}
+
+/// 查找标签的结束位置,忽略属性值引号中的`>`
+fn find_tag_end(input: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in input.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attributes(input: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_string();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            match after.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let end = after[1..].find(q).map(|i| i + 1).unwrap_or(after.len());
+                    let value = &after[1..end];
+                    rest = after.get(end + 1..).unwrap_or("");
+                    value
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    let value = &after[..end];
+                    rest = &after[end..];
+                    value
+                }
+            }
+        } else {
+            ""
+        };
+
+        if !key.is_empty() {
+            attributes.push((key, decode_entities(value)));
+        }
+        rest = rest.trim_start();
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(name: &str, attributes: &[(&str, &str)], self_closing: bool) -> Token {
+        Token::Start {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            self_closing,
+        }
+    }
+
+    #[test]
+    fn tokenize_tags_and_text() {
+        let tokens =
+            tokenize(r#"<DT><A HREF="https://a.com/?x=1&amp;y=2" ADD_DATE=1>A &lt;b&gt;</A>"#);
+        assert_eq!(
+            tokens,
+            vec![
+                start("dt", &[], false),
+                start(
+                    "a",
+                    &[("HREF", "https://a.com/?x=1&y=2"), ("ADD_DATE", "1")],
+                    false
+                ),
+                Token::Text("A <b>".to_string()),
+                Token::End {
+                    name: "a".to_string()
+                },
+            ]
+        );
+        assert_eq!(tokens[1].attribute("href"), Some("https://a.com/?x=1&y=2"));
+    }
+
+    #[test]
+    fn tokenize_skips_comments_and_declarations() {
+        let tokens = tokenize("<?xml version=\"1.0\"?><!DOCTYPE x><!-- <a> --><b/>");
+        assert_eq!(tokens, vec![start("b", &[], true)]);
+    }
+
+    #[test]
+    fn tokenize_quoted_greater_than() {
+        let tokens = tokenize(r#"<a title="1 > 0" x='y'>"#);
+        assert_eq!(
+            tokens,
+            vec![start("a", &[("title", "1 > 0"), ("x", "y")], false)]
+        );
+    }
+
+    #[test]
+    fn tokenize_cdata() {
+        let tokens = tokenize("<t><![CDATA[a <b> &amp;]]></t>");
+        assert_eq!(tokens[1], Token::Text("a <b> &amp;".to_string()));
+    }
+
+    #[test]
+    fn decode_numeric_entities() {
+        assert_eq!(
+            decode_entities("&#65;&#x42;&unknown; & x"),
+            "AB&unknown; & x"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bookmarks;
+pub mod desktop_entry;
+mod markup;
+pub mod recently_used;
@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::OneClickLaunchError;
+
+use super::markup::{self, Token};
+
+/// 最近使用的文件
+#[derive(Debug, Clone)]
+pub struct RecentFile {
+    /// 本地文件路径
+    pub path: String,
+    /// 最后一次被使用的时间(Unix秒)
+    pub last_used: i64,
+    /// 使用过该文件的应用程序名称
+    pub applications: Vec<String>,
+}
+
+/// freedesktop最近使用文件列表: $XDG_DATA_HOME/recently-used.xbel,默认为~/.local/share/recently-used.xbel
+pub fn default_file() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("recently-used.xbel"))
+}
+
+/// 读取并解析最近使用文件列表
+pub fn parse_file(path: &Path) -> Result<Vec<RecentFile>, OneClickLaunchError> {
+    let content = fs::read_to_string(path)?;
+    parse(&content)
+}
+
+/// 解析XBEL格式的最近使用文件列表,只保留本地文件(file://)
+pub fn parse(content: &str) -> Result<Vec<RecentFile>, OneClickLaunchError> {
+    let tokens = markup::tokenize(content);
+
+    if !tokens
+        .iter()
+        .any(|t| matches!(t, Token::Start { name, .. } if name == "xbel"))
+    {
+        return Err(OneClickLaunchError::ExecutionError(
+            "不是有效的XBEL文件".to_string(),
+        ));
+    }
+
+    let mut files = vec![];
+    let mut current: Option<RecentFile> = None;
+
+    for token in tokens.iter() {
+        match token {
+            Token::Start {
+                name, self_closing, ..
+            } if name == "bookmark" => {
+                let path = token.attribute("href").and_then(file_uri_to_path);
+                let last_used = ["modified", "visited", "added"]
+                    .iter()
+                    .filter_map(|key| token.attribute(key).and_then(parse_timestamp))
+                    .max()
+                    .unwrap_or_default();
+                current = path.map(|path| RecentFile {
+                    path,
+                    last_used,
+                    applications: vec![],
+                });
+                if *self_closing {
+                    files.extend(current.take());
+                }
+            }
+            Token::End { name } if name == "bookmark" => {
+                files.extend(current.take());
+            }
+            Token::Start { name, .. } if name == "bookmark:application" => {
+                if let Some(file) = current.as_mut() {
+                    if let Some(app) = token.attribute("name").filter(|n| !n.is_empty()) {
+                        file.applications.push(app.to_string());
+                    }
+                    if let Some(modified) = token.attribute("modified").and_then(parse_timestamp) {
+                        file.last_used = file.last_used.max(modified);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    files.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+
+    Ok(files)
+}
+
+/// 把file://地址转换为本地路径,并解码百分号转义
+fn file_uri_to_path(uri: &str) -> Option<String> {
+    let encoded = uri.strip_prefix("file://")?;
+    // 去除主机名部分,如file://localhost/home
+    let encoded = &encoded[encoded.find('/')?..];
+
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[index + 1..index + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// 解析RFC 3339时间(如`2024-05-01T08:30:00.123456Z`),返回Unix秒
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (date, time) = value.split_once(['T', 't', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+
+    // 拆分时区: Z 或 ±HH:MM
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else if let Some(index) = time.rfind(['+', '-']) {
+        let (clock, offset) = time.split_at(index);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "0"));
+        let offset_secs =
+            sign * (hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60);
+        (clock, offset_secs)
+    } else {
+        (time, 0)
+    };
+
+    let clock = clock.split('.').next()?;
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    let second: i64 = clock_parts.next().unwrap_or("0").parse().ok()?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(
+        days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second
+            - offset_secs,
+    )
+}
+
+/// 计算公历日期距1970-01-01的天数
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_utc() {
+        assert_eq!(parse_timestamp("2024-05-01T08:30:00Z"), Some(1714552200));
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+    }
+
+    #[test]
+    fn parse_timestamp_offset() {
+        assert_eq!(
+            parse_timestamp("2024-05-01T10:30:00+02:00"),
+            Some(1714552200)
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01T03:00:00-05:30"),
+            Some(1714552200)
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_fractional_seconds() {
+        assert_eq!(
+            parse_timestamp("2024-05-01T08:30:00.123456Z"),
+            Some(1714552200)
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_invalid() {
+        assert_eq!(parse_timestamp("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("not a time"), None);
+    }
+
+    #[test]
+    fn days_from_civil_before_march() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(2024, 2, 29), 19782);
+        assert_eq!(days_from_civil(2024, 3, 1), 19783);
+    }
+
+    #[test]
+    fn file_uri_to_path_decodes() {
+        assert_eq!(
+            file_uri_to_path("file:///home/user/My%20Notes.txt").as_deref(),
+            Some("/home/user/My Notes.txt")
+        );
+        assert_eq!(
+            file_uri_to_path("file://localhost/home/user/a%20b").as_deref(),
+            Some("/home/user/a b")
+        );
+        assert_eq!(file_uri_to_path("https://example.com/a"), None);
+    }
+}
@@ -1,14 +1,23 @@
 use anyhow::Result;
 use api::window_api::{ScaleFactorChangedState, setup_tray};
-use api::{launcher_api, setting_api, window_api};
-use constants::AUTO_START_FLAG;
-use db::{launcher, launcher_resource, settings};
-use events::EventDispatcher;
+use api::{
+    backup_api, data_dir_api, hook_api, import_api, launcher_api, profile_api, search_api,
+    setting_api, sync_api, transfer_api, trash_api, undo_api, window_api,
+};
+use clap::Parser;
+use cli::Cli;
+use constants::{AUTO_START_FLAG, SCHEMA_VERSION};
+use db::{
+    folder, hook, launcher, launcher_resource, resource_path_override, search_index, settings,
+    sync, tag, undo_journal,
+};
 use events::system_listeners::register_system_listeners;
 use events::types::{ApplicationStartupComplete, ApplicationStartupCompletePayload};
+use events::{EventDispatcher, ListenerRegistry};
+use resource_handler::ResourceHandlerRegistry;
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
 use std::{env, fs};
 use tauri::Emitter;
 use tauri::tray::TrayIcon;
@@ -16,15 +25,73 @@ use tauri::{AppHandle, Manager};
 use tauri_plugin_autostart::MacosLauncher;
 use tracing::{debug, info};
 
-use crate::constants::LAUNCH_SPECIFIED_LAUNCHER_KEY;
 mod api;
+mod cli;
 mod constants;
 mod db;
 pub mod error;
 mod events;
+mod importer;
+mod paths;
+mod profile;
+mod resource_handler;
+mod setting_registry;
 
 pub struct DatabaseManager {
-    pub pool: SqlitePool,
+    /// 数据目录
+    pub data_dir: PathBuf,
+    state: RwLock<DatabaseState>,
+}
+
+/// 当前配置使用的数据库,切换配置时整体替换
+struct DatabaseState {
+    pool: SqlitePool,
+    /// 数据库文件路径
+    db_path: PathBuf,
+    /// 当前配置id
+    profile_id: String,
+}
+
+impl DatabaseManager {
+    pub fn new(data_dir: PathBuf, pool: SqlitePool, db_path: PathBuf, profile_id: String) -> Self {
+        Self {
+            data_dir,
+            state: RwLock::new(DatabaseState {
+                pool,
+                db_path,
+                profile_id,
+            }),
+        }
+    }
+
+    /// 当前配置的数据库连接池
+    pub fn pool(&self) -> SqlitePool {
+        self.state.read().unwrap().pool.clone()
+    }
+
+    /// 当前配置的数据库文件路径
+    pub fn db_path(&self) -> PathBuf {
+        self.state.read().unwrap().db_path.clone()
+    }
+
+    /// 当前配置的数据库连接池与数据库文件路径,保证两者属于同一个配置
+    pub fn pool_and_path(&self) -> (SqlitePool, PathBuf) {
+        let state = self.state.read().unwrap();
+        (state.pool.clone(), state.db_path.clone())
+    }
+
+    /// 当前配置id
+    pub fn profile_id(&self) -> String {
+        self.state.read().unwrap().profile_id.clone()
+    }
+
+    /// 切换到另一个配置的数据库,返回原来的连接池
+    pub fn replace(&self, pool: SqlitePool, db_path: PathBuf, profile_id: String) -> SqlitePool {
+        let mut state = self.state.write().unwrap();
+        state.db_path = db_path;
+        state.profile_id = profile_id;
+        std::mem::replace(&mut state.pool, pool)
+    }
 }
 
 #[derive(Clone, serde::Serialize)]
@@ -37,38 +104,38 @@ pub struct WindowContext {
     pub tray_icon: TrayIcon,
 }
 
-#[cfg(not(feature = "portable"))]
-fn get_db_path() -> Result<PathBuf> {
-    // 获取用户的 AppData 目录路径
-    let app_data = env::var("APPDATA")?;
+/// 确定数据目录并打开命令行指定配置的数据库
+async fn open_data(cli: &Cli) -> Result<DatabaseManager> {
+    let data_dir = paths::data_dir(cli.data_dir.as_deref())?;
 
-    // 拼接到特定的文件夹路径
-    let db_path = PathBuf::from(app_data)
-        .join("one_click_launch")
-        .join("data")
-        .join("one_click_launch.db");
+    // 发布版本没有控制台,日志写入数据目录,钩子与启动命令的输出也记录在其中
+    #[cfg(not(debug_assertions))]
+    init_file_log(&data_dir);
 
-    Ok(db_path)
+    init_db(data_dir, cli.profile.as_deref(), cli.is_interactive()).await
 }
 
-#[cfg(feature = "portable")]
-fn get_db_path() -> Result<PathBuf> {
-    // 便携版：使用当前可执行文件的目录
-    let exe_path = env::current_exe()?;
-    let db_path = exe_path
-        .parent()
-        .ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::Other, "Cannot get executable directory")
-        })?
-        .join("data")
-        .join("one_click_launch.db");
-
-    Ok(db_path)
-}
+/// 打开指定配置的数据库,`profile`为空时使用上次激活的配置
+async fn init_db(
+    data_dir: PathBuf,
+    profile: Option<&str>,
+    interactive: bool,
+) -> Result<DatabaseManager> {
+    let profile = profile::resolve(&data_dir, profile)?;
+
+    info!("使用配置: {} ({})", profile.name, profile.id);
+
+    let db_path = profile::db_path(&data_dir, &profile.id);
 
-async fn init_db() -> Result<DatabaseManager> {
-    let db_path = get_db_path()?;
+    let pool = open_database(&db_path, interactive).await?;
 
+    Ok(DatabaseManager::new(data_dir, pool, db_path, profile.id))
+}
+
+/// 打开数据库并初始化表结构,数据库文件不存在时创建
+///
+/// `interactive`为false时不弹窗,数据库文件损坏时直接返回错误
+pub async fn open_database(db_path: &Path, interactive: bool) -> Result<SqlitePool> {
     debug!("db_path:{:?}", db_path);
 
     // 确保目录存在
@@ -85,58 +152,125 @@ async fn init_db() -> Result<DatabaseManager> {
         info!("Database file created at {:?}", db_path);
     }
 
+    // 检查数据库文件是否损坏,损坏时询问用户是否从备份恢复
+    backup_api::ensure_database_integrity(db_path, interactive).await?;
+
     // 创建连接池
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
         .connect(db_path.to_string_lossy().as_ref())
         .await?;
 
+    // 数据库结构需要升级时,先备份升级前的数据库
+    let (user_version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+        .fetch_one(&pool)
+        .await?;
+    let (table_count,): (i64,) = sqlx::query_as("SELECT count(*) FROM sqlite_master")
+        .fetch_one(&pool)
+        .await?;
+    if user_version < SCHEMA_VERSION && table_count > 0 {
+        backup_api::backup_database(&pool, db_path, "pre-migration").await?;
+        backup_api::rotate_backups(&pool, db_path).await?;
+    }
+
     launcher::initialize(&pool).await?;
 
     launcher_resource::initialize(&pool).await?;
 
+    resource_path_override::initialize(&pool).await?;
+
+    folder::initialize(&pool).await?;
+
+    tag::initialize(&pool).await?;
+
+    sync::initialize(&pool).await?;
+
+    search_index::initialize(&pool, user_version).await?;
+
     settings::initialize(&pool).await?;
 
-    Ok(DatabaseManager { pool })
+    undo_journal::initialize(&pool).await?;
+
+    hook::initialize(&pool).await?;
+
+    sqlx::query(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+        .execute(&pool)
+        .await?;
+
+    Ok(pool)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub async fn run() -> Result<()> {
-    let db_manager = init_db().await?;
+    // 参数无效或指定了--help时输出提示后退出
+    let cli = Cli::parse();
 
-    tauri::Builder::default()
-        .setup(move |app| {
-            // 注册监听器,之后添加新的监听器时在这个方法内部添加
-            register_listeners(app.handle());
+    // 命令行指定了配置时使用该配置,否则使用上次激活的配置
+    let db_manager = match open_data(&cli).await {
+        Ok(db_manager) => db_manager,
+        Err(e) => {
+            cli::print_error(cli.json, &error::OneClickLaunchError::from(e));
+            std::process::exit(cli::EXIT_FAILURE);
+        }
+    };
 
-            // 初始化窗口
-            setup_tray(app.handle())?;
+    // 列表、导入导出等命令行命令无需启动窗口,执行完成后直接退出;
+    // 修改了启动器时继续向下执行,由单例插件通知运行中的应用程序刷新
+    if let Some(code) = cli::run_headless_command(&cli, &db_manager.pool()).await {
+        if code != cli::EXIT_SUCCESS || !cli.modifies_launchers() {
+            std::process::exit(code);
+        }
+    }
 
-            // 当所有初始化都完成后发送应用程序启动完成事件
-            EventDispatcher::<ApplicationStartupComplete>::send_event(
-                app.handle(),
-                ApplicationStartupCompletePayload {
-                    args: env::args().collect(),
-                },
-            )?;
+    if let Some(code) = cli::check_launch_target(&cli, &db_manager.pool()).await {
+        std::process::exit(code);
+    }
 
-            Ok(())
-        })
-        .on_window_event(window_api::handle_window_event)
-        .manage(db_manager)
-        .manage(ScaleFactorChangedState {
-            last_reset: Mutex::new(None),
-        })
+    // 以JSON格式输出启动报告时在当前进程中启动,不转发给运行中的应用程序
+    let report_launcher = cli.launcher_to_report().map(str::to_string);
+
+    let mut builder = tauri::Builder::default();
+    if report_launcher.is_none() {
         // 必须优先注册单例插件
-        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
             info!("run app: {}, {argv:?}, {cwd}", app.package_info().name);
 
-            if let Some(Ok(launcher_id)) = extract_arg_value(&argv, &LAUNCH_SPECIFIED_LAUNCHER_KEY)
-                .map(|value| value.parse::<i64>())
+            let cli = Cli::from_argv(&argv);
+
+            if cli.as_ref().is_some_and(Cli::is_quit) {
+                app.exit(0);
+                return;
+            }
+
+            // 其他进程通过命令行修改了启动器,只需刷新,不切换配置也不显示窗口
+            if cli.as_ref().is_some_and(Cli::modifies_launchers) {
+                let app_cloned = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = launcher_api::notify_launchers_changed(&app_cloned).await {
+                        tracing::error!("刷新启动器失败: {}", e);
+                    }
+                });
+                return;
+            }
+
+            // 再次运行时指定了配置则切换到该配置
+            if let Some(profile) = cli.as_ref().and_then(|cli| cli.profile.clone()) {
+                let app_cloned = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = profile_api::switch_to(&app_cloned, &profile).await {
+                        tracing::error!("切换配置失败: {}", e);
+                    }
+                });
+            }
+
+            if let Some(launcher) = cli
+                .as_ref()
+                .and_then(Cli::launcher_to_launch)
+                .map(str::to_string)
             {
                 let app_cloned = app.clone();
                 tokio::spawn(async move {
-                    if let Err(e) = launcher_api::launch(app_cloned, launcher_id).await {
+                    if let Err(e) = launcher_api::launch_by_query(app_cloned, &launcher).await {
                         tracing::error!("launcher launch fail: {}", e);
                     }
                 });
@@ -152,7 +286,49 @@ pub async fn run() -> Result<()> {
             }
             app.emit("single-instance", Payload { args: argv, cwd })
                 .unwrap();
-        }))
+        }));
+    }
+
+    builder
+        .setup(move |app| {
+            // 没有运行中的应用程序时quit命令与修改启动器的命令无需处理
+            if cli.is_quit() || cli.modifies_launchers() {
+                app.handle().exit(0);
+                return Ok(());
+            }
+
+            if let Some(launcher) = report_launcher {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let code = cli::launch_and_report(&app_handle, &launcher).await;
+                    app_handle.exit(code);
+                });
+                return Ok(());
+            }
+
+            // 注册监听器,之后添加新的监听器时在这个方法内部添加
+            register_listeners(app.handle());
+
+            // 初始化窗口
+            setup_tray(app.handle())?;
+
+            // 当所有初始化都完成后发送应用程序启动完成事件
+            EventDispatcher::<ApplicationStartupComplete>::send_event(
+                app.handle(),
+                ApplicationStartupCompletePayload {
+                    args: env::args().collect(),
+                },
+            )?;
+
+            Ok(())
+        })
+        .on_window_event(window_api::handle_window_event)
+        .manage(db_manager)
+        .manage(ListenerRegistry::default())
+        .manage(ResourceHandlerRegistry::default())
+        .manage(ScaleFactorChangedState {
+            last_reset: Mutex::new(None),
+        })
         .plugin(tauri_plugin_window_state::Builder::default().build())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -173,33 +349,105 @@ pub async fn run() -> Result<()> {
             launcher_api::add_resources,
             launcher_api::modify_resource_name,
             launcher_api::modify_resource_path,
+            launcher_api::modify_resource_path_override,
+            launcher_api::query_hostname,
             launcher_api::delete_resource,
             launcher_api::query_launchers,
             launcher_api::launch,
             launcher_api::open_path,
+            launcher_api::check_resource,
             launcher_api::create_handler_shortcut,
+            launcher_api::create_folder,
+            launcher_api::modify_folder_name,
+            launcher_api::move_folder,
+            launcher_api::delete_folder,
+            launcher_api::query_folders,
+            launcher_api::move_launcher_to_folder,
+            launcher_api::modify_launcher_tags,
+            launcher_api::query_tags,
+            launcher_api::modify_tag_name,
+            launcher_api::delete_tag,
+            launcher_api::modify_launcher_notes,
+            launcher_api::modify_launcher_launch_commands,
+            launcher_api::modify_launcher_slug,
+            search_api::search,
+            transfer_api::export_launchers,
+            transfer_api::import_launchers,
+            import_api::scan_desktop_entries,
+            import_api::import_desktop_entries,
+            import_api::parse_bookmarks,
+            import_api::import_bookmarks,
+            import_api::query_recent_files,
+            import_api::create_launcher_from_recent_files,
+            trash_api::query_trash,
+            trash_api::restore_launcher,
+            trash_api::restore_resource,
+            trash_api::purge_launcher,
+            trash_api::purge_resource,
+            undo_api::undo,
+            undo_api::redo,
+            undo_api::query_undo_state,
+            backup_api::create_backup,
+            backup_api::list_backups,
+            backup_api::restore_backup,
+            sync_api::sync_now,
+            profile_api::query_profiles,
+            profile_api::create_profile,
+            profile_api::rename_profile,
+            profile_api::delete_profile,
+            profile_api::switch_profile,
+            data_dir_api::query_data_dir,
+            data_dir_api::migrate_data_dir,
+            hook_api::query_hook_events,
+            hook_api::query_hooks,
+            hook_api::create_hook,
+            hook_api::modify_hook,
+            hook_api::delete_hook,
+            sync_api::query_sync_conflicts,
+            sync_api::clear_sync_conflicts,
             setting_api::save_setting,
             setting_api::read_setting,
             setting_api::read_all_setting,
+            setting_api::read_all_typed_setting,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
     Ok(())
 }
 
+/// 日志文件超过该大小(字节)时,启动时把它重命名为`.old`后重新记录
+#[cfg(not(debug_assertions))]
+const LOG_FILE_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 把日志写入数据目录下的`logs/one_click_launch.log`,无法创建日志文件时不记录日志
+#[cfg(not(debug_assertions))]
+fn init_file_log(data_dir: &Path) {
+    let log_dir = data_dir.join("logs");
+    if fs::create_dir_all(&log_dir).is_err() {
+        return;
+    }
+
+    let log_path = log_dir.join("one_click_launch.log");
+    if fs::metadata(&log_path).is_ok_and(|m| m.len() > LOG_FILE_MAX_BYTES) {
+        let _ = fs::rename(&log_path, log_dir.join("one_click_launch.log.old"));
+    }
+
+    let Ok(file) = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+    else {
+        return;
+    };
+
+    let _ = tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(tracing::Level::INFO)
+        .try_init();
+}
+
 fn register_listeners(app: &AppHandle) {
     // 注册系统级别的监听器
     register_system_listeners(app);
 }
-
-pub fn extract_arg_value(argv: &[String], key: &str) -> Option<String> {
-    let mut iter = argv.iter();
-    while let Some(arg) = iter.next() {
-        if arg == key {
-            if let Some(val) = iter.next() {
-                return Some(val.clone());
-            }
-        }
-    }
-    None
-}
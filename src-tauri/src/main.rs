@@ -6,6 +6,13 @@ use tracing_subscriber::fmt;
 
 #[tokio::main]
 async fn main() -> Result<(), OneClickLaunchError> {
+    // 发布版本没有控制台窗口,从终端运行时附加到终端,使命令行的输出与错误可见
+    #[cfg(all(windows, not(debug_assertions)))]
+    unsafe {
+        use windows::Win32::System::Console::{ATTACH_PARENT_PROCESS, AttachConsole};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
     // 只在开发模式下调用日志初始化
     #[cfg(debug_assertions)]
     {
@@ -0,0 +1,126 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::constants::DATA_DIR_ENV;
+
+/// 资源路径中表示可执行文件所在目录的占位符,便携版在不同主机上挂载位置不同时仍能找到资源
+pub const APP_DIR_TOKEN: &str = "$APP_DIR";
+
+/// 便携版标记文件,可执行文件所在目录下存在该文件时以便携版运行
+pub const PORTABLE_MARKER_FILE: &str = "portable";
+
+/// 数据目录,按以下顺序确定:
+///
+/// 1. 命令行参数`--data-dir <目录>`
+/// 2. 环境变量`ONE_CLICK_LAUNCH_DATA_DIR`
+/// 3. 便携版为可执行文件所在目录下的`data`目录,
+///    安装版为系统的用户数据目录(Windows为`%APPDATA%`,Linux为`$XDG_DATA_HOME`或`~/.local/share`)下的`one_click_launch/data`
+pub fn data_dir(arg: Option<&Path>) -> Result<PathBuf> {
+    if let Some(dir) = arg.filter(|d| !d.as_os_str().is_empty()) {
+        return Ok(dir.to_path_buf());
+    }
+
+    if let Some(dir) = env::var_os(DATA_DIR_ENV).filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    default_data_dir()
+}
+
+/// 未指定数据目录时使用的目录,根据是否以便携版运行选择
+pub fn default_data_dir() -> Result<PathBuf> {
+    if is_portable() {
+        portable_data_dir()
+    } else {
+        installed_data_dir()
+    }
+}
+
+/// 是否以便携版运行: 可执行文件所在目录下存在标记文件或`data`目录
+pub fn is_portable() -> bool {
+    let Ok(exe_dir) = exe_dir() else {
+        return false;
+    };
+    exe_dir.join(PORTABLE_MARKER_FILE).is_file() || exe_dir.join("data").is_dir()
+}
+
+/// 便携版数据目录: 可执行文件所在目录下的`data`目录
+pub fn portable_data_dir() -> Result<PathBuf> {
+    Ok(exe_dir()?.join("data"))
+}
+
+/// 安装版数据目录: 系统的用户数据目录下的`one_click_launch/data`
+pub fn installed_data_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| anyhow!("无法获取用户数据目录"))?
+        .join("one_click_launch")
+        .join("data");
+
+    Ok(data_dir)
+}
+
+/// 可执行文件所在目录
+pub fn exe_dir() -> Result<PathBuf> {
+    let exe_path = env::current_exe()?;
+    let exe_dir = exe_path
+        .parent()
+        .ok_or_else(|| anyhow!("Cannot get executable directory"))?
+        .to_path_buf();
+
+    Ok(exe_dir)
+}
+
+/// 把路径开头的`$APP_DIR`替换为可执行文件所在目录,路径可以用引号包裹
+pub fn expand_app_dir(path: &str) -> Result<String> {
+    let (quote, rest) = split_quote(path);
+    let Some(remaining) = rest.strip_prefix(APP_DIR_TOKEN) else {
+        return Ok(path.to_string());
+    };
+
+    Ok(format!("{}{}{}", quote, exe_dir_str()?, remaining))
+}
+
+/// 路径位于可执行文件所在目录下时,返回以`$APP_DIR`开头的相对路径
+pub fn to_app_dir_relative(path: &str) -> Option<String> {
+    let exe_dir = exe_dir_str().ok()?;
+    let (quote, rest) = split_quote(path);
+
+    let head = rest.get(..exe_dir.len())?;
+    let same_dir = if cfg!(windows) {
+        head.eq_ignore_ascii_case(&exe_dir)
+    } else {
+        head == exe_dir
+    };
+    if !same_dir {
+        return None;
+    }
+
+    // 只匹配完整的目录名,避免把`D:\app2`当作`D:\app`下的路径
+    let remaining = &rest[exe_dir.len()..];
+    if !remaining.is_empty() && !remaining.starts_with(['/', '\\']) {
+        return None;
+    }
+
+    Some(format!("{}{}{}", quote, APP_DIR_TOKEN, remaining))
+}
+
+/// 可执行文件所在目录,去掉Windows的`\\?\`前缀
+fn exe_dir_str() -> Result<String> {
+    let exe_dir = exe_dir()?.to_string_lossy().to_string();
+    Ok(exe_dir
+        .strip_prefix(r"\\?\")
+        .map(str::to_string)
+        .unwrap_or(exe_dir))
+}
+
+/// 分离路径开头的引号
+fn split_quote(path: &str) -> (&str, &str) {
+    match path.chars().next() {
+        Some(quote @ ('"' | '\'')) => path.split_at(quote.len_utf8()),
+        _ => ("", path),
+    }
+}
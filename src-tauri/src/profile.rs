@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// 默认配置,使用原有的数据库文件
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// 配置列表文件名
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// 配置,每个配置使用独立的数据库
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+}
+
+/// 配置列表,保存在数据目录下的`profiles.json`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileStore {
+    /// 启动时使用的配置id
+    pub active: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![default_profile()],
+        }
+    }
+}
+
+impl ProfileStore {
+    /// 按id或名称(不区分大小写)查找配置
+    pub fn find(&self, id_or_name: &str) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|p| p.id == id_or_name)
+            .or_else(|| {
+                self.profiles
+                    .iter()
+                    .find(|p| p.name.to_lowercase() == id_or_name.to_lowercase())
+            })
+    }
+
+    /// 是否已存在同名(不区分大小写)的其他配置
+    pub fn name_taken(&self, name: &str, except_id: Option<&str>) -> bool {
+        self.profiles.iter().any(|p| {
+            Some(p.id.as_str()) != except_id && p.name.to_lowercase() == name.to_lowercase()
+        })
+    }
+}
+
+/// 读取配置列表,文件不存在时返回只包含默认配置的列表
+pub fn load(data_dir: &Path) -> Result<ProfileStore> {
+    let path = data_dir.join(PROFILES_FILE_NAME);
+    if !path.exists() {
+        return Ok(ProfileStore::default());
+    }
+
+    let mut store: ProfileStore = serde_json::from_str(&fs::read_to_string(&path)?)?;
+
+    if !store.profiles.iter().any(|p| p.id == DEFAULT_PROFILE_ID) {
+        store.profiles.insert(0, default_profile());
+    }
+    if !store.profiles.iter().any(|p| p.id == store.active) {
+        store.active = DEFAULT_PROFILE_ID.to_string();
+    }
+
+    Ok(store)
+}
+
+/// 保存配置列表
+pub fn save(data_dir: &Path, store: &ProfileStore) -> Result<()> {
+    fs::create_dir_all(data_dir)?;
+
+    // 先写入临时文件再重命名,避免写入中断导致配置列表损坏
+    let path = data_dir.join(PROFILES_FILE_NAME);
+    let tmp_path = data_dir.join(format!("{}.tmp", PROFILES_FILE_NAME));
+    fs::write(&tmp_path, serde_json::to_string_pretty(store)?)?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(())
+}
+
+/// 查找要使用的配置,`selected`为空时使用上次激活的配置
+pub fn resolve(data_dir: &Path, selected: Option<&str>) -> Result<Profile> {
+    let store = load(data_dir)?;
+
+    let profile = match selected {
+        Some(selected) => store
+            .find(selected)
+            .ok_or_else(|| anyhow!("配置不存在: {}", selected))?,
+        None => store
+            .find(&store.active)
+            .ok_or_else(|| anyhow!("配置不存在: {}", store.active))?,
+    };
+
+    Ok(profile.clone())
+}
+
+/// 配置使用的数据库文件路径
+///
+/// 默认配置使用数据目录下原有的数据库文件,其他配置的数据库保存在`profiles`目录下
+pub fn db_path(data_dir: &Path, profile_id: &str) -> PathBuf {
+    if profile_id == DEFAULT_PROFILE_ID {
+        data_dir.join("one_click_launch.db")
+    } else {
+        data_dir.join("profiles").join(format!("{}.db", profile_id))
+    }
+}
+
+fn default_profile() -> Profile {
+    Profile {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        name: "默认".to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+use std::{path::Path, process::Command};
+
+use tauri::AppHandle;
+
+use crate::error::OneClickLaunchError;
+
+use super::ResourceHandler;
+
+/// 可执行程序: 路径的第一部分是存在的文件(.exe/.bat/.sh 等)时,作为命令执行,其余部分作为参数
+pub struct ExecutableHandler;
+
+impl ExecutableHandler {
+    fn split(path: &str) -> Option<(String, Vec<String>)> {
+        let mut parts = shlex::split(path)?;
+        if parts.is_empty() {
+            return None;
+        }
+        let program = parts.remove(0);
+        Some((program, parts))
+    }
+}
+
+impl ResourceHandler for ExecutableHandler {
+    fn name(&self) -> &'static str {
+        "executable"
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        Self::split(path).is_some_and(|(program, _)| Path::new(&program).is_file())
+    }
+
+    fn launch(&self, _app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
+        let (program, args) = Self::split(path)
+            .ok_or_else(|| OneClickLaunchError::ExecutionError("无法解析路径".to_string()))?;
+        Command::new(program).args(args).spawn()?;
+        Ok(())
+    }
+
+    fn describe(&self, path: &str) -> String {
+        match Self::split(path) {
+            Some((program, args)) if !args.is_empty() => {
+                format!("程序: {} (参数: {})", program, args.join(" "))
+            }
+            Some((program, _)) => format!("程序: {}", program),
+            None => "程序".to_string(),
+        }
+    }
+
+    fn validate(&self, path: &str) -> Result<(), OneClickLaunchError> {
+        match Self::split(path) {
+            Some((program, _)) if Path::new(&program).is_file() => Ok(()),
+            Some((program, _)) => Err(OneClickLaunchError::ExecutionError(format!(
+                "程序不存在: {}",
+                program
+            ))),
+            None => Err(OneClickLaunchError::ExecutionError(
+                "无法解析路径".to_string(),
+            )),
+        }
+    }
+}
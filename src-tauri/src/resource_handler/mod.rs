@@ -0,0 +1,92 @@
+pub mod executable;
+pub mod opener;
+pub mod script;
+
+use std::sync::{Arc, RwLock};
+
+use tauri::AppHandle;
+
+use crate::error::OneClickLaunchError;
+
+/// 资源处理器,负责识别并启动一类资源
+///
+/// 新的资源类型只需实现该trait并注册到`ResourceHandlerRegistry`,无需修改启动流程
+pub trait ResourceHandler: Send + Sync {
+    /// 处理器名称
+    fn name(&self) -> &'static str;
+
+    /// 是否能处理该资源路径
+    fn matches(&self, path: &str) -> bool;
+
+    /// 启动资源
+    fn launch(&self, app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError>;
+
+    /// 资源的简短说明,用于界面展示
+    fn describe(&self, path: &str) -> String;
+
+    /// 检查资源当前是否可以启动
+    fn validate(&self, path: &str) -> Result<(), OneClickLaunchError>;
+
+    /// 启动失败时是否不再尝试其他处理器,启动过程有副作用的处理器需要返回true
+    fn exclusive(&self) -> bool {
+        false
+    }
+}
+
+/// 资源处理器注册表,作为全局状态注册到应用程序中
+///
+/// 后注册的处理器优先匹配;处理器启动失败时依次尝试其他能处理该资源的处理器,
+/// 内置的`opener`处理器能处理任意资源,作为最后的兜底
+pub struct ResourceHandlerRegistry {
+    handlers: RwLock<Vec<Arc<dyn ResourceHandler>>>,
+}
+
+impl Default for ResourceHandlerRegistry {
+    fn default() -> Self {
+        let registry = Self {
+            handlers: RwLock::new(vec![]),
+        };
+        registry.register(Arc::new(opener::OpenerHandler));
+        registry.register(Arc::new(executable::ExecutableHandler));
+        registry.register(Arc::new(script::ScriptHandler));
+        registry
+    }
+}
+
+impl ResourceHandlerRegistry {
+    /// 注册处理器
+    pub fn register(&self, handler: Arc<dyn ResourceHandler>) {
+        self.handlers.write().unwrap().push(handler);
+    }
+
+    /// 能处理该资源的处理器,按优先级排列
+    pub fn matching(&self, path: &str) -> Vec<Arc<dyn ResourceHandler>> {
+        self.handlers
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|handler| handler.matches(path))
+            .cloned()
+            .collect()
+    }
+
+    /// 启动资源
+    pub fn launch(&self, app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
+        let mut last_error = None;
+        for handler in self.matching(path) {
+            match handler.launch(app, path) {
+                Ok(()) => return Ok(()),
+                Err(e) if handler.exclusive() => return Err(e),
+                Err(e) => {
+                    tracing::debug!("{} 启动资源失败: {e:?}，尝试其他处理器", handler.name());
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            OneClickLaunchError::ExecutionError(format!("没有能处理该资源的处理器: {}", path))
+        }))
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::error::OneClickLaunchError;
+
+use super::ResourceHandler;
+
+/// 使用系统默认的程序打开文件、文件夹或网址,能处理任意资源
+pub struct OpenerHandler;
+
+impl OpenerHandler {
+    fn is_url(path: &str) -> bool {
+        path.contains("://")
+    }
+}
+
+impl ResourceHandler for OpenerHandler {
+    fn name(&self) -> &'static str {
+        "opener"
+    }
+
+    fn matches(&self, _path: &str) -> bool {
+        true
+    }
+
+    fn launch(&self, app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
+        app.opener()
+            .open_path(path, None::<&str>)
+            .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn describe(&self, path: &str) -> String {
+        if Self::is_url(path) {
+            "网址".to_string()
+        } else if Path::new(path).is_dir() {
+            "文件夹".to_string()
+        } else {
+            "文件".to_string()
+        }
+    }
+
+    fn validate(&self, path: &str) -> Result<(), OneClickLaunchError> {
+        if Self::is_url(path) || Path::new(path).exists() {
+            Ok(())
+        } else {
+            Err(OneClickLaunchError::ExecutionError(format!(
+                "路径不存在: {}",
+                path
+            )))
+        }
+    }
+}
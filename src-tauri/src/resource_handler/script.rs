@@ -0,0 +1,245 @@
+use std::{
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rhai::{Dynamic, Engine, EvalAltResult, Position, module_resolvers::DummyModuleResolver};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+use crate::{error::OneClickLaunchError, paths};
+
+use super::{ResourceHandler, ResourceHandlerRegistry};
+
+/// 脚本文件扩展名
+const SCRIPT_EXTENSION: &str = "rhai";
+
+/// 脚本的最长执行时间(秒),包括等待的时间
+const SCRIPT_TIMEOUT_SECS: u64 = 120;
+
+/// 脚本最多执行的操作数,防止死循环占用CPU
+const SCRIPT_MAX_OPERATIONS: u64 = 10_000_000;
+
+/// 检查端口时的连接超时时间(毫秒)
+const PORT_CONNECT_TIMEOUT_MILLIS: u64 = 500;
+
+/// 等待时检查条件的间隔(毫秒)
+const POLL_INTERVAL_MILLIS: u64 = 200;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Rhai脚本: 扩展名为`.rhai`的文件,在沙箱中执行,脚本中可以使用以下函数:
+///
+/// - `launch(path)`: 与启动器中的资源一样启动网址、文件或程序(可带参数),不能启动其他脚本
+/// - `sleep(millis)`: 等待指定的毫秒数
+/// - `file_exists(path)`: 文件或文件夹是否存在
+/// - `port_open(host, port)`: 端口是否可以连接
+/// - `wait_for_port(host, port, millis)`/`wait_for_file(path, millis)`: 等待端口可连接或文件出现,超时返回false
+/// - `var(name)`: 读取变量,`APP_DIR`/`SCRIPT_DIR`/`HOSTNAME`为内置变量,其余读取环境变量,不存在时返回空字符串
+/// - `log(message)`/`print(message)`: 写入日志
+///
+/// 脚本本身不能读写文件、通过`import`加载其他脚本或动态执行代码,执行时间超过限制时中止;
+/// 但`launch`可以运行任意已存在的程序,因此只应执行可信的脚本
+pub struct ScriptHandler;
+
+impl ScriptHandler {
+    fn is_script(path: &str) -> bool {
+        Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(SCRIPT_EXTENSION))
+    }
+
+    /// 创建禁止加载模块与动态执行代码的引擎
+    fn sandbox_engine() -> Engine {
+        let mut engine = Engine::new();
+        // 默认的模块解析器会按路径加载文件,禁止`import`
+        engine.set_module_resolver(DummyModuleResolver::new());
+        // 禁止动态执行代码
+        engine.disable_symbol("eval");
+        engine
+    }
+
+    /// 创建沙箱引擎,`deadline`之后中止脚本
+    fn engine(app: &AppHandle, script: &Path, deadline: Instant) -> Engine {
+        let mut engine = Self::sandbox_engine();
+
+        engine
+            .set_max_operations(SCRIPT_MAX_OPERATIONS)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(64 * 1024)
+            .set_max_array_size(10_000)
+            .set_max_map_size(10_000);
+
+        engine.on_progress(move |_| (Instant::now() > deadline).then(|| Dynamic::from("timeout")));
+
+        let name = script.to_string_lossy().to_string();
+        let print_name = name.clone();
+        engine.on_print(move |message| info!("脚本 {}: {}", print_name, message));
+        let debug_name = name.clone();
+        engine.on_debug(move |message, _, pos| info!("脚本 {} {}: {}", debug_name, pos, message));
+        let log_name = name;
+        engine.register_fn("log", move |message: &str| {
+            info!("脚本 {}: {}", log_name, message)
+        });
+
+        let launch_app = app.clone();
+        engine.register_fn("launch", move |path: &str| -> ScriptResult<()> {
+            let path = paths::expand_app_dir(path).map_err(|e| e.to_string())?;
+            if Self::is_script(&path) {
+                return Err(format!("脚本中不能启动其他脚本: {}", path).into());
+            }
+            launch_app
+                .state::<ResourceHandlerRegistry>()
+                .launch(&launch_app, &path)
+                .map_err(|e| format!("启动资源失败: {}, {}", path, e).into())
+        });
+
+        engine.register_fn("sleep", move |millis: i64| -> ScriptResult<()> {
+            sleep_until(
+                deadline,
+                Instant::now() + Duration::from_millis(millis.max(0) as u64),
+            )
+        });
+
+        engine.register_fn("file_exists", |path: &str| -> ScriptResult<bool> {
+            let path = paths::expand_app_dir(path).map_err(|e| e.to_string())?;
+            Ok(Path::new(&path).exists())
+        });
+
+        engine.register_fn("port_open", |host: &str, port: i64| -> ScriptResult<bool> {
+            port_open(host, port)
+        });
+
+        engine.register_fn(
+            "wait_for_port",
+            move |host: &str, port: i64, millis: i64| -> ScriptResult<bool> {
+                wait_until(deadline, millis, || port_open(host, port))
+            },
+        );
+
+        engine.register_fn(
+            "wait_for_file",
+            move |path: &str, millis: i64| -> ScriptResult<bool> {
+                let path = paths::expand_app_dir(path).map_err(|e| e.to_string())?;
+                wait_until(deadline, millis, || Ok(Path::new(&path).exists()))
+            },
+        );
+
+        let script_dir = script
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        engine.register_fn("var", move |name: &str| -> String {
+            match name {
+                "APP_DIR" => paths::exe_dir()
+                    .map(|dir| dir.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                "SCRIPT_DIR" => script_dir.clone(),
+                "HOSTNAME" => tauri_plugin_os::hostname(),
+                _ => std::env::var(name).unwrap_or_default(),
+            }
+        });
+
+        engine
+    }
+
+    fn run(&self, app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
+        let script = PathBuf::from(path);
+        let deadline = Instant::now() + Duration::from_secs(SCRIPT_TIMEOUT_SECS);
+        let engine = Self::engine(app, &script, deadline);
+
+        info!("执行脚本: {}", path);
+        engine.run_file(script).map_err(|e| match *e {
+            EvalAltResult::ErrorTerminated(_, pos) => OneClickLaunchError::ExecutionError(format!(
+                "脚本执行超时({}秒): {} {}",
+                SCRIPT_TIMEOUT_SECS, path, pos
+            )),
+            e => OneClickLaunchError::ExecutionError(format!("脚本执行失败: {} {}", path, e)),
+        })
+    }
+}
+
+impl ResourceHandler for ScriptHandler {
+    fn name(&self) -> &'static str {
+        "script"
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        Self::is_script(path) && Path::new(path).is_file()
+    }
+
+    /// 在当前线程执行脚本直到结束,脚本中的等待会阻塞调用方
+    fn launch(&self, app: &AppHandle, path: &str) -> Result<(), OneClickLaunchError> {
+        self.run(app, path)
+    }
+
+    /// 脚本可能已经启动了部分资源,失败时不能再用其他方式打开
+    fn exclusive(&self) -> bool {
+        true
+    }
+
+    fn describe(&self, path: &str) -> String {
+        format!("脚本: {}", path)
+    }
+
+    fn validate(&self, path: &str) -> Result<(), OneClickLaunchError> {
+        if !Path::new(path).is_file() {
+            return Err(OneClickLaunchError::ExecutionError(format!(
+                "脚本不存在: {}",
+                path
+            )));
+        }
+        Self::sandbox_engine()
+            .compile_file(PathBuf::from(path))
+            .map(|_| ())
+            .map_err(|e| OneClickLaunchError::ExecutionError(format!("脚本有语法错误: {}", e)))
+    }
+}
+
+fn port_open(host: &str, port: i64) -> ScriptResult<bool> {
+    let port = u16::try_from(port).map_err(|_| format!("端口无效: {}", port))?;
+    let addrs: Vec<SocketAddr> = match (host, port).to_socket_addrs() {
+        Ok(addrs) => addrs.collect(),
+        Err(_) => return Ok(false),
+    };
+    let connect_timeout = Duration::from_millis(PORT_CONNECT_TIMEOUT_MILLIS);
+    Ok(addrs
+        .iter()
+        .any(|addr| TcpStream::connect_timeout(addr, connect_timeout).is_ok()))
+}
+
+/// 等待到`until`,超过脚本的执行时间限制时返回错误
+fn sleep_until(deadline: Instant, until: Instant) -> ScriptResult<()> {
+    if until > deadline {
+        return Err(
+            EvalAltResult::ErrorTerminated(Dynamic::from("timeout"), Position::NONE).into(),
+        );
+    }
+    thread::sleep(until.saturating_duration_since(Instant::now()));
+    Ok(())
+}
+
+/// 每隔一段时间检查条件,条件满足时返回true,等待`millis`毫秒后仍不满足时返回false
+fn wait_until(
+    deadline: Instant,
+    millis: i64,
+    mut check: impl FnMut() -> ScriptResult<bool>,
+) -> ScriptResult<bool> {
+    let until = Instant::now() + Duration::from_millis(millis.max(0) as u64);
+    loop {
+        if check()? {
+            return Ok(true);
+        }
+        let now = Instant::now();
+        if now >= until {
+            return Ok(false);
+        }
+        sleep_until(
+            deadline,
+            (now + Duration::from_millis(POLL_INTERVAL_MILLIS)).min(until),
+        )?;
+    }
+}
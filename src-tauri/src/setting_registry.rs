@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite};
+use tracing::warn;
+
+use crate::{
+    constants::{
+        AUTO_START_LAUNCHER_IDS_KEY, BACKUP_INTERVAL_HOURS_KEY, BACKUP_KEEP_COUNT_KEY,
+        CLOSE_MAIN_PANEL_EXIT, CLOSE_MAIN_PANEL_KEY, CLOSE_MAIN_PANEL_MINIMIZE, EDIT_MODE_KEY,
+        HIDE_AFTER_AUTO_START_KEY, LAUNCH_THEN_EXIT_KEY, SYNC_FOLDER_KEY, SYNC_INTERVAL_SECS_KEY,
+        SYNC_MACHINE_ID_KEY, THEME_KEY, TRASH_RETENTION_DAYS_KEY, UNDO_JOURNAL_LIMIT_KEY,
+    },
+    db::settings,
+    error::OneClickLaunchError,
+};
+
+/// 设置项的类型
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SettingKind {
+    /// 布尔值,保存为`true`或`false`
+    Bool,
+    /// 闭区间内的整数
+    Integer { min: i64, max: i64 },
+    /// 任意字符串
+    String,
+    /// 只能取给定值之一的字符串
+    Choice { options: &'static [&'static str] },
+    /// id列表,保存为JSON数组
+    IdList,
+}
+
+/// 类型化的设置值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SettingValue {
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    IdList(Vec<i64>),
+}
+
+/// 设置项定义
+#[derive(Debug, Clone, Copy)]
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    /// 默认值的存储形式
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// 全部已知的设置项,每个设置项都有固定的类型与默认值,保存时会校验取值
+pub static SETTING_DEFINITIONS: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: THEME_KEY,
+        kind: SettingKind::Choice {
+            options: &["light", "dark", "system"],
+        },
+        default: "light",
+        description: "主题",
+    },
+    SettingDefinition {
+        key: CLOSE_MAIN_PANEL_KEY,
+        kind: SettingKind::Choice {
+            options: &[CLOSE_MAIN_PANEL_MINIMIZE, CLOSE_MAIN_PANEL_EXIT],
+        },
+        default: CLOSE_MAIN_PANEL_MINIMIZE,
+        description: "关闭主面板时最小化到托盘(m1)或退出程序(m2)",
+    },
+    SettingDefinition {
+        key: AUTO_START_LAUNCHER_IDS_KEY,
+        kind: SettingKind::IdList,
+        default: "[]",
+        description: "随开机自启一同启动的启动器",
+    },
+    SettingDefinition {
+        key: HIDE_AFTER_AUTO_START_KEY,
+        kind: SettingKind::Bool,
+        default: "false",
+        description: "开机自启后隐藏主面板",
+    },
+    SettingDefinition {
+        key: LAUNCH_THEN_EXIT_KEY,
+        kind: SettingKind::Bool,
+        default: "false",
+        description: "启动启动器后退出程序",
+    },
+    SettingDefinition {
+        key: EDIT_MODE_KEY,
+        kind: SettingKind::Bool,
+        default: "true",
+        description: "主面板处于编辑模式",
+    },
+    SettingDefinition {
+        key: TRASH_RETENTION_DAYS_KEY,
+        kind: SettingKind::Integer { min: 0, max: 3650 },
+        default: "30",
+        description: "回收站保留天数,为0时不自动清理",
+    },
+    SettingDefinition {
+        key: UNDO_JOURNAL_LIMIT_KEY,
+        kind: SettingKind::Integer { min: 1, max: 10000 },
+        default: "100",
+        description: "撤销记录保留条数",
+    },
+    SettingDefinition {
+        key: BACKUP_INTERVAL_HOURS_KEY,
+        kind: SettingKind::Integer { min: 0, max: 8760 },
+        default: "24",
+        description: "定时备份间隔(小时),为0时不自动备份",
+    },
+    SettingDefinition {
+        key: BACKUP_KEEP_COUNT_KEY,
+        kind: SettingKind::Integer { min: 1, max: 1000 },
+        default: "7",
+        description: "备份保留数量",
+    },
+    SettingDefinition {
+        key: SYNC_FOLDER_KEY,
+        kind: SettingKind::String,
+        default: "",
+        description: "同步文件夹,为空时不同步",
+    },
+    SettingDefinition {
+        key: SYNC_MACHINE_ID_KEY,
+        kind: SettingKind::String,
+        default: "",
+        description: "本机在同步文件夹中的设备id",
+    },
+    SettingDefinition {
+        key: SYNC_INTERVAL_SECS_KEY,
+        kind: SettingKind::Integer { min: 1, max: 86400 },
+        default: "30",
+        description: "检查同步文件夹的间隔(秒)",
+    },
+];
+
+/// 查找设置项定义
+pub fn find_definition(key: &str) -> Option<&'static SettingDefinition> {
+    SETTING_DEFINITIONS.iter().find(|d| d.key == key)
+}
+
+impl SettingDefinition {
+    /// 把存储形式的值解析为类型化的值
+    pub fn parse(&self, raw: &str) -> Result<SettingValue, OneClickLaunchError> {
+        let invalid = |reason: String| {
+            OneClickLaunchError::ExecutionError(format!(
+                "设置项 {} 的值无效: {:?}, {}",
+                self.key, raw, reason
+            ))
+        };
+
+        match self.kind {
+            SettingKind::Bool => match raw.trim().to_lowercase().as_str() {
+                "true" => Ok(SettingValue::Bool(true)),
+                "false" => Ok(SettingValue::Bool(false)),
+                _ => Err(invalid("需要 true 或 false".to_string())),
+            },
+            SettingKind::Integer { min, max } => {
+                let value = raw
+                    .trim()
+                    .parse::<i64>()
+                    .map_err(|e| invalid(e.to_string()))?;
+                if value < min || value > max {
+                    return Err(invalid(format!("需要在 {} 到 {} 之间", min, max)));
+                }
+                Ok(SettingValue::Integer(value))
+            }
+            SettingKind::String => Ok(SettingValue::String(raw.to_string())),
+            SettingKind::Choice { options } => options
+                .iter()
+                .find(|option| **option == raw.trim())
+                .map(|option| SettingValue::String(option.to_string()))
+                .ok_or_else(|| invalid(format!("需要是 {:?} 之一", options))),
+            SettingKind::IdList => serde_json::from_str::<Vec<i64>>(raw)
+                .map(SettingValue::IdList)
+                .map_err(|e| invalid(e.to_string())),
+        }
+    }
+
+    /// 默认值
+    pub fn default_value(&self) -> SettingValue {
+        self.parse(self.default)
+            .expect("设置项的默认值必须能通过校验")
+    }
+
+    /// 校验并返回规范化后的存储形式
+    pub fn normalize(&self, raw: &str) -> Result<String, OneClickLaunchError> {
+        let value = match self.parse(raw)? {
+            SettingValue::Bool(value) => value.to_string(),
+            SettingValue::Integer(value) => value.to_string(),
+            SettingValue::String(value) => value,
+            SettingValue::IdList(ids) => serde_json::to_string(&ids)
+                .map_err(|e| OneClickLaunchError::ExecutionError(e.to_string()))?,
+        };
+        Ok(value)
+    }
+}
+
+/// 整数设置项的默认值,用于读取设置失败时
+pub fn default_integer(key: &str) -> i64 {
+    match find_definition(key).map(SettingDefinition::default_value) {
+        Some(SettingValue::Integer(value)) => value,
+        _ => panic!("{} 不是已定义的整数设置项", key),
+    }
+}
+
+/// 校验要保存的设置,返回规范化后的值,未知的设置项会被拒绝
+pub fn validate(key: &str, raw: &str) -> Result<String, OneClickLaunchError> {
+    find_definition(key)
+        .ok_or_else(|| OneClickLaunchError::ExecutionError(format!("未知的设置项: {}", key)))?
+        .normalize(raw)
+}
+
+/// 读取设置的类型化值,未保存或保存的值无效时返回默认值
+pub async fn read<'a, E>(executor: E, key: &str) -> Result<SettingValue, OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let definition = find_definition(key)
+        .ok_or_else(|| OneClickLaunchError::ExecutionError(format!("未知的设置项: {}", key)))?;
+
+    let value = match settings::read(executor, key).await? {
+        Some(setting) => definition.parse(&setting.value).unwrap_or_else(|e| {
+            warn!("{}, 使用默认值 {:?}", e, definition.default);
+            definition.default_value()
+        }),
+        None => definition.default_value(),
+    };
+
+    Ok(value)
+}
+
+/// 读取布尔类型的设置
+pub async fn read_bool<'a, E>(executor: E, key: &str) -> Result<bool, OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    match read(executor, key).await? {
+        SettingValue::Bool(value) => Ok(value),
+        other => Err(type_mismatch(key, &other)),
+    }
+}
+
+/// 读取整数类型的设置
+pub async fn read_integer<'a, E>(executor: E, key: &str) -> Result<i64, OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    match read(executor, key).await? {
+        SettingValue::Integer(value) => Ok(value),
+        other => Err(type_mismatch(key, &other)),
+    }
+}
+
+/// 读取字符串类型的设置
+pub async fn read_string<'a, E>(executor: E, key: &str) -> Result<String, OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    match read(executor, key).await? {
+        SettingValue::String(value) => Ok(value),
+        other => Err(type_mismatch(key, &other)),
+    }
+}
+
+/// 读取id列表类型的设置
+pub async fn read_id_list<'a, E>(executor: E, key: &str) -> Result<Vec<i64>, OneClickLaunchError>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    match read(executor, key).await? {
+        SettingValue::IdList(value) => Ok(value),
+        other => Err(type_mismatch(key, &other)),
+    }
+}
+
+fn type_mismatch(key: &str, value: &SettingValue) -> OneClickLaunchError {
+    OneClickLaunchError::ExecutionError(format!("设置项 {} 的类型不匹配: {:?}", key, value))
+}